@@ -0,0 +1,205 @@
+//! Benchmarks trace generation, proving and verification of Rescue hash chains.
+//!
+//! Starting from a baseline configuration, each sweep varies one parameter: the chain length,
+//! the blowup factor, the number of queries, the FRI folding factor or the commitment hash
+//! function. For every configuration, the median time of each stage is reported together with
+//! the size and conjectured security of the proof.
+//!
+//! Run with `cargo bench --bench hash_chain [-- FILTER]`, where FILTER selects configurations
+//! whose description contains it, e.g. `blowup=16` or `sha3-256`. The number of samples per
+//! configuration can be changed with the `BENCH_SAMPLES` environment variable. When the target
+//! is run by `cargo test`, only the smallest configuration is run once.
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use stark_test::{
+    build_trace, verify_do_work_with, Blake3, CommitmentHasher, DoWorkProver, HashFunction,
+    Rescue4x14, SecurityPolicy,
+};
+use winterfell::{
+    crypto::hashers::{Blake3_192, Sha3_256},
+    math::fields::f128::BaseElement,
+    FieldExtension, ProofOptions, Prover,
+};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+const DEFAULT_SAMPLES: usize = 10;
+
+// CONFIGURATIONS
+// ================================================================================================
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Config {
+    iterations: usize,
+    blowup_factor: usize,
+    num_queries: usize,
+    folding_factor: usize,
+    hash_fn: HashFunction,
+}
+
+const BASELINE: Config = Config {
+    iterations: 256,
+    blowup_factor: 8,
+    num_queries: 32,
+    folding_factor: 8,
+    hash_fn: HashFunction::Blake3_256,
+};
+
+impl Config {
+    fn options(&self) -> ProofOptions {
+        ProofOptions::new(
+            self.num_queries,
+            self.blowup_factor,
+            0,
+            FieldExtension::None,
+            self.folding_factor,
+            31,
+        )
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "n={} blowup={} queries={} folding={} {}",
+            self.iterations, self.blowup_factor, self.num_queries, self.folding_factor, self.hash_fn
+        )
+    }
+}
+
+/// Returns configurations of all sweeps, without duplicates.
+fn sweeps() -> Vec<Config> {
+    let mut configs = Vec::new();
+    for iterations in [64, 256, 1024, 4096] {
+        configs.push(Config { iterations, ..BASELINE });
+    }
+    for blowup_factor in [4, 8, 16, 32] {
+        configs.push(Config { blowup_factor, ..BASELINE });
+    }
+    for num_queries in [16, 32, 64] {
+        configs.push(Config { num_queries, ..BASELINE });
+    }
+    for folding_factor in [2, 4, 8, 16] {
+        configs.push(Config { folding_factor, ..BASELINE });
+    }
+    for hash_fn in [HashFunction::Blake3_256, HashFunction::Blake3_192, HashFunction::Sha3_256] {
+        configs.push(Config { hash_fn, ..BASELINE });
+    }
+
+    let mut unique: Vec<Config> = Vec::new();
+    for config in configs {
+        if !unique.contains(&config) {
+            unique.push(config);
+        }
+    }
+    unique
+}
+
+// MEASUREMENTS
+// ================================================================================================
+
+struct Measurement {
+    build_trace: Duration,
+    prove: Duration,
+    verify: Duration,
+    proof_size: usize,
+    security: u32,
+}
+
+fn measure(config: &Config, samples: usize) -> Measurement {
+    match config.hash_fn {
+        HashFunction::Blake3_256 => measure_with::<Blake3>(config, samples),
+        HashFunction::Blake3_192 => measure_with::<Blake3_192<BaseElement>>(config, samples),
+        HashFunction::Sha3_256 => measure_with::<Sha3_256<BaseElement>>(config, samples),
+    }
+}
+
+fn measure_with<H: CommitmentHasher>(config: &Config, samples: usize) -> Measurement {
+    // weak configurations are part of the sweeps, so the verifier accepts proofs of any security
+    let policy = SecurityPolicy::MinConjectured(0);
+
+    let mut build_times = Vec::with_capacity(samples);
+    let mut prove_times = Vec::with_capacity(samples);
+    let mut verify_times = Vec::with_capacity(samples);
+    let mut proof_size = 0;
+    let mut security = 0;
+    for _ in 0..samples {
+        let start_time = Instant::now();
+        let trace = build_trace::<Rescue4x14>(SEED, config.iterations);
+        build_times.push(start_time.elapsed());
+
+        let prover = DoWorkProver::<Rescue4x14, H>::new(config.options(), config.iterations);
+        let pub_inputs = prover.get_pub_inputs(&trace);
+        let start_time = Instant::now();
+        let proof = prover.prove(trace).expect("failed to generate proof");
+        prove_times.push(start_time.elapsed());
+        proof_size = proof.to_bytes().len();
+
+        let start_time = Instant::now();
+        let level = verify_do_work_with::<H>(pub_inputs, proof, &policy)
+            .expect("failed to verify proof");
+        verify_times.push(start_time.elapsed());
+        security = level.conjectured;
+    }
+
+    Measurement {
+        build_trace: median(build_times),
+        prove: median(prove_times),
+        verify: median(verify_times),
+        proof_size,
+        security,
+    }
+}
+
+fn median(mut times: Vec<Duration>) -> Duration {
+    times.sort();
+    let mid = times.len() / 2;
+    if times.len().is_multiple_of(2) {
+        (times[mid - 1] + times[mid]) / 2
+    } else {
+        times[mid]
+    }
+}
+
+// MAIN
+// ================================================================================================
+
+fn main() {
+    // cargo passes --bench only when the target is run by `cargo bench`
+    let args: Vec<String> = env::args().skip(1).collect();
+    let is_bench = args.iter().any(|arg| arg == "--bench");
+    let filter = args.iter().find(|arg| !arg.starts_with("--"));
+
+    let (configs, samples) = if is_bench {
+        let samples = env::var("BENCH_SAMPLES")
+            .ok()
+            .map(|samples| samples.parse().expect("BENCH_SAMPLES must be a positive integer"))
+            .unwrap_or(DEFAULT_SAMPLES);
+        assert!(samples > 0, "BENCH_SAMPLES must be a positive integer");
+        (sweeps(), samples)
+    } else {
+        (vec![Config { iterations: 4, ..BASELINE }], 1)
+    };
+
+    println!("Median of {samples} samples per configuration");
+    println!(
+        "{:<50} {:>12} {:>12} {:>12} {:>10} {:>9}",
+        "configuration", "build_trace", "prove", "verify", "size (B)", "security"
+    );
+    for config in configs {
+        let description = config.description();
+        if filter.is_some_and(|filter| !description.contains(filter.as_str())) {
+            continue;
+        }
+        let m = measure(&config, samples);
+        println!(
+            "{:<50} {:>12} {:>12} {:>12} {:>10} {:>9}",
+            description,
+            format!("{:.2?}", m.build_trace),
+            format!("{:.2?}", m.prove),
+            format!("{:.2?}", m.verify),
+            m.proof_size,
+            format!("{} bits", m.security),
+        );
+    }
+}
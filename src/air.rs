@@ -1,3 +1,5 @@
+use core::marker::PhantomData;
+
 use winterfell::{
     math::{fields::f128::BaseElement, FieldElement},
     Air, AirContext, Assertion,
@@ -5,61 +7,55 @@ use winterfell::{
     TraceInfo, TransitionConstraintDegree,
 };
 
-use crate::{PublicInputs, TRACE_WIDTH};
-use crate::rescue::{CYCLE_LENGTH, enforce_round, get_round_constants};
+use crate::rescue::Rescue4x14;
+use crate::utils::permutation::AlgebraicPermutation;
 use crate::utils::{are_equal, is_zero, not, EvaluationResult};
+use crate::{result_step, PublicInputs};
 
-// CONSTANTS
+// HASH CHAIN AIR
 // ================================================================================================
 
-/// Specifies steps on which Rescue transition function is applied.
-const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ZERO,
-    BaseElement::ZERO,
-];
-
-pub struct TrainAir {
+/// AIR for a chain of hashes `result = H^iterations(seed)`, where `H` applies the permutation `P`
+/// to a state holding the 2-element input followed by zeros and returns the first two elements.
+pub struct TrainAir<P: AlgebraicPermutation = Rescue4x14> {
     context: AirContext<BaseElement>,
     seed: [BaseElement; 2],
     result: [BaseElement; 2],
+    result_step: usize,
+    _permutation: PhantomData<P>,
 }
 
-impl Air for TrainAir {
+impl<P: AlgebraicPermutation> Air for TrainAir<P> {
     type BaseField = BaseElement;
     type PublicInputs = PublicInputs;
     type GkrProof = ();
     type GkrVerifier = ();
 
     fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
-        assert_eq!(TRACE_WIDTH, trace_info.width());
+        assert_eq!(P::STATE_WIDTH, trace_info.width());
+        assert!(pub_inputs.iterations > 0, "number of iterations must be greater than zero");
+        let result_step = result_step::<P>(pub_inputs.iterations);
+        assert!(
+            result_step < trace_info.length(),
+            "trace of length {} cannot hold {} iterations",
+            trace_info.length(),
+            pub_inputs.iterations
+        );
 
         let degrees = vec![
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
+            TransitionConstraintDegree::with_cycles(P::CONSTRAINT_DEGREE, vec![P::CYCLE_LENGTH]);
+            P::STATE_WIDTH
         ];
 
-        let num_assertions = 4;
+        // the seed and the zero capacity at the first step, and the result
+        let num_assertions = P::STATE_WIDTH + 2;
 
         TrainAir {
             context: AirContext::new(trace_info, degrees, num_assertions, options),
             seed: pub_inputs.seed,
             result: pub_inputs.result,
+            result_step,
+            _permutation: PhantomData,
         }
     }
 
@@ -72,16 +68,16 @@ impl Air for TrainAir {
         let current = frame.current();
         let next = frame.next();
 
-        // expected state width is 4 field elements
-        debug_assert_eq!(TRACE_WIDTH, current.len());
-        debug_assert_eq!(TRACE_WIDTH, next.len());
+        // expected state width is equal to the width of the permutation state
+        debug_assert_eq!(P::STATE_WIDTH, current.len());
+        debug_assert_eq!(P::STATE_WIDTH, next.len());
 
         // split periodic values into hash_flag and Rescue round constants
         let hash_flag = periodic_values[0];
         let ark = &periodic_values[1..];
 
         // when hash_flag = 1, constraints for Rescue round are enforced
-        enforce_round(result, current, next, ark, hash_flag);
+        P::enforce_round(result, current, next, ark, hash_flag);
 
         // when hash_flag = 0, constraints for copying hash values to the next
         // step are enforced.
@@ -90,15 +86,18 @@ impl Air for TrainAir {
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
-        // for our computation to be valid, value in column 0 at step 0 must be equal to the
-        // starting value, and at the last step it must be equal to the result.
-        let last_step = self.trace_length() - 1;
-        vec![
+        // for our computation to be valid, the state at step 0 must hold the seed followed by
+        // zeros, and at the end of the last hash cycle it must hold the result.
+        let mut assertions = vec![
             Assertion::single(0, 0, self.seed[0]),
             Assertion::single(1, 0, self.seed[1]),
-            Assertion::single(0, last_step, self.result[0]),
-            Assertion::single(1, last_step, self.result[1]),
-        ]
+        ];
+        for column in 2..P::STATE_WIDTH {
+            assertions.push(Assertion::single(column, 0, BaseElement::ZERO));
+        }
+        assertions.push(Assertion::single(0, self.result_step, self.result[0]));
+        assertions.push(Assertion::single(1, self.result_step, self.result[1]));
+        assertions
     }
 
     fn context(&self) -> &AirContext<Self::BaseField> {
@@ -106,8 +105,8 @@ impl Air for TrainAir {
     }
 
     fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
-        let mut result = vec![CYCLE_MASK.to_vec()];
-        result.append(&mut get_round_constants());
+        let mut result = vec![P::get_round_mask()];
+        result.append(&mut P::get_round_constants());
         result
     }
 }
@@ -117,10 +116,17 @@ impl Air for TrainAir {
 
 /// when flag = 1, enforces that the next state of the computation is defined like so:
 /// - the first two registers are equal to the values from the previous step
-/// - the other two registers are equal to 0
-fn enforce_hash_copy<E: FieldElement>(result: &mut [E], current: &[E], next: &[E], flag: E) {
+/// - the other registers are equal to 0
+#[allow(clippy::needless_range_loop)]
+pub(crate) fn enforce_hash_copy<E: FieldElement>(
+    result: &mut [E],
+    current: &[E],
+    next: &[E],
+    flag: E,
+) {
     result.agg_constraint(0, flag, are_equal(current[0], next[0]));
     result.agg_constraint(1, flag, are_equal(current[1], next[1]));
-    result.agg_constraint(2, flag, is_zero(next[2]));
-    result.agg_constraint(3, flag, is_zero(next[3]));
+    for i in 2..result.len() {
+        result.agg_constraint(i, flag, is_zero(next[i]));
+    }
 }
@@ -0,0 +1,143 @@
+use core::marker::PhantomData;
+
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+use super::{batch_layout, chain_index, BatchPublicInputs};
+use crate::air::enforce_hash_copy;
+use crate::utils::not;
+use crate::{result_step, trace_length, AlgebraicPermutation, Rescue4x14};
+
+// BATCH AIR
+// ================================================================================================
+
+/// AIR for a batch of independent hash chains `results[i] = H^iterations(seeds[i])` laid out as
+/// described by [batch_layout], where `H` is defined as in [TrainAir](crate::TrainAir).
+pub struct BatchAir<P: AlgebraicPermutation = Rescue4x14> {
+    context: AirContext<BaseElement>,
+    seeds: Vec<[BaseElement; 2]>,
+    results: Vec<[BaseElement; 2]>,
+    groups: usize,
+    segments: usize,
+    segment_length: usize,
+    result_step: usize,
+    _permutation: PhantomData<P>,
+}
+
+impl<P: AlgebraicPermutation> Air for BatchAir<P> {
+    type BaseField = BaseElement;
+    type PublicInputs = BatchPublicInputs;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: BatchPublicInputs, options: ProofOptions) -> Self {
+        let (groups, segments) = batch_layout::<P>(pub_inputs.batch_size());
+        assert_eq!(groups * P::STATE_WIDTH, trace_info.width());
+        assert!(pub_inputs.iterations > 0, "number of iterations must be greater than zero");
+        let segment_length = trace_length::<P>(pub_inputs.iterations).expect("too many iterations");
+        assert_eq!(
+            Some(trace_info.length()),
+            segment_length.checked_mul(segments),
+            "trace of length {} cannot hold {} chains of {} iterations",
+            trace_info.length(),
+            pub_inputs.batch_size(),
+            pub_inputs.iterations
+        );
+
+        let degrees = vec![
+            TransitionConstraintDegree::with_cycles(P::CONSTRAINT_DEGREE, vec![P::CYCLE_LENGTH]);
+            trace_info.width()
+        ];
+
+        // the seeds, zero capacities and results of all chains in a column group are asserted
+        // once for every segment
+        let num_assertions = (P::STATE_WIDTH + 2) * groups;
+
+        BatchAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            seeds: pub_inputs.seeds,
+            results: pub_inputs.results,
+            groups,
+            segments,
+            segment_length,
+            result_step: result_step::<P>(pub_inputs.iterations),
+            _permutation: PhantomData,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        // expected state width is equal to the combined width of all column groups
+        debug_assert_eq!(self.groups * P::STATE_WIDTH, current.len());
+        debug_assert_eq!(self.groups * P::STATE_WIDTH, next.len());
+
+        // split periodic values into hash_flag, copy_flag and Rescue round constants; these are
+        // shared by all chains. copy_flag is zero on the last step of every segment, where the
+        // chains of the next segment start
+        let hash_flag = periodic_values[0];
+        let copy_flag = periodic_values[1];
+        let ark = &periodic_values[2..];
+
+        // every chain is constrained in the same way as in TrainAir
+        let chains = result
+            .chunks_mut(P::STATE_WIDTH)
+            .zip(current.chunks(P::STATE_WIDTH))
+            .zip(next.chunks(P::STATE_WIDTH));
+        for ((result, current), next) in chains {
+            P::enforce_round(result, current, next, ark, hash_flag);
+            enforce_hash_copy(result, current, next, copy_flag);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // every chain must start with its seed followed by zeros and end with its result; the
+        // values for all segments of a column group are asserted with a stride of one segment
+        let stride = self.segment_length;
+        let mut assertions = Vec::with_capacity((P::STATE_WIDTH + 2) * self.groups);
+        for group in 0..self.groups {
+            let chains: Vec<usize> = (0..self.segments)
+                .map(|segment| chain_index(self.seeds.len(), self.segments, group, segment))
+                .collect();
+            let values = |values: &[[BaseElement; 2]], i: usize| {
+                chains.iter().map(|&chain| values[chain][i]).collect::<Vec<_>>()
+            };
+
+            let column = group * P::STATE_WIDTH;
+            assertions.push(Assertion::sequence(column, 0, stride, values(&self.seeds, 0)));
+            assertions.push(Assertion::sequence(column + 1, 0, stride, values(&self.seeds, 1)));
+            for capacity in column + 2..column + P::STATE_WIDTH {
+                assertions.push(Assertion::periodic(capacity, 0, stride, BaseElement::ZERO));
+            }
+            for i in 0..2 {
+                let results = values(&self.results, i);
+                assertions.push(Assertion::sequence(column + i, self.result_step, stride, results));
+            }
+        }
+        assertions
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let round_mask = P::get_round_mask();
+        let mut copy_mask: Vec<BaseElement> =
+            (0..self.segment_length).map(|step| not(round_mask[step % P::CYCLE_LENGTH])).collect();
+        copy_mask[self.segment_length - 1] = BaseElement::ZERO;
+
+        let mut result = vec![round_mask, copy_mask];
+        result.append(&mut P::get_round_constants());
+        result
+    }
+}
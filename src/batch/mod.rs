@@ -0,0 +1,300 @@
+//! Proves many independent hash chains in a single proof.
+//!
+//! The chains are laid out side by side and one after another: the execution trace is split into
+//! groups of `STATE_WIDTH` columns and into segments of equal length, and every segment of a
+//! column group holds one chain which is constrained in the same way as the trace of
+//! [TrainAir](crate::TrainAir). Chain `i` occupies segment `i % segments` of group
+//! `i / segments`; see [batch_layout]. All chains in a batch have the same number of iterations.
+//!
+//! At most [max_batch_width] chains fit side by side, i.e. 63 chains of 4-wide Rescue hashes;
+//! larger batches are spread over more segments, so that a single proof covers any batch whose
+//! trace fits into the evaluation domain.
+
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement, ToElements},
+    Proof, ProofOptions, Prover, TraceInfo, TraceTable,
+};
+
+use crate::security::verify_with_policy;
+use crate::{
+    check_blowup_factor, check_trace_length, trace_length, AirId,
+    AlgebraicPermutation, Blake3, BundlePublicInputs, Error, Rescue4x14, SecurityLevel,
+    SecurityPolicy,
+};
+
+mod air;
+pub use air::BatchAir;
+
+mod prover;
+pub use prover::BatchProver;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Seeds and results of a batch of hash chains; every seed has exactly one result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchPublicInputs {
+    seeds: Vec<[BaseElement; 2]>,
+    results: Vec<[BaseElement; 2]>,
+    iterations: usize,
+}
+
+impl BatchPublicInputs {
+    /// Returns public inputs for hash chains with `results[i] = H^iterations(seeds[i])`.
+    ///
+    /// # Errors
+    /// Returns an error if `seeds` is empty, or if the number of results differs from the number
+    /// of seeds.
+    pub fn new(
+        seeds: Vec<[BaseElement; 2]>,
+        results: Vec<[BaseElement; 2]>,
+        iterations: usize,
+    ) -> Result<Self, Error> {
+        if seeds.is_empty() || seeds.len() != results.len() {
+            return Err(Error::PublicInputMismatch(format!(
+                "batch has {} seeds and {} results",
+                seeds.len(),
+                results.len()
+            )));
+        }
+        Ok(BatchPublicInputs { seeds, results, iterations })
+    }
+
+    /// Returns the seeds of the hash chains, one per chain.
+    pub fn seeds(&self) -> &[[BaseElement; 2]] {
+        &self.seeds
+    }
+
+    /// Returns the results of the hash chains, in the order of the seeds.
+    pub fn results(&self) -> &[[BaseElement; 2]] {
+        &self.results
+    }
+
+    /// Returns the number of chained Rescue hashes in every chain.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the number of hash chains in the batch.
+    pub fn batch_size(&self) -> usize {
+        self.seeds.len()
+    }
+}
+
+impl ToElements<BaseElement> for BatchPublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.seeds.concat();
+        result.extend_from_slice(&self.results.concat());
+        result.push(BaseElement::from(self.iterations as u64));
+        result
+    }
+}
+
+impl Serializable for BatchPublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_usize(self.seeds.len());
+        for (seed, result) in self.seeds.iter().zip(&self.results) {
+            target.write_many(seed);
+            target.write_many(result);
+        }
+        target.write_u64(self.iterations as u64);
+    }
+}
+
+impl Deserializable for BatchPublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let batch_size = source.read_usize()?;
+        if batch_size == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "batch size must be greater than zero".to_string(),
+            ));
+        }
+
+        // the batch size is not bounded by the trace, so memory is only taken for chains which
+        // were actually read
+        let mut seeds = Vec::new();
+        let mut results = Vec::new();
+        for _ in 0..batch_size {
+            seeds.push(source.read()?);
+            results.push(source.read()?);
+        }
+
+        let iterations = source.read_u64()?;
+        let iterations = usize::try_from(iterations).map_err(|_| {
+            DeserializationError::InvalidValue(format!(
+                "{iterations} iterations do not fit into usize"
+            ))
+        })?;
+        Ok(BatchPublicInputs { seeds, results, iterations })
+    }
+}
+
+impl BundlePublicInputs for BatchPublicInputs {
+    const AIR_ID: AirId = AirId::Batch;
+}
+
+// TRACE BUILDER
+// ================================================================================================
+
+/// Returns the maximum number of hash chains which fit side by side into a single trace.
+///
+/// Proofs for traces of [TraceInfo::MAX_TRACE_WIDTH] columns cannot be deserialized, so the
+/// widest usable trace is one column narrower.
+pub fn max_batch_width<P: AlgebraicPermutation>() -> usize {
+    (TraceInfo::MAX_TRACE_WIDTH - 1) / P::STATE_WIDTH
+}
+
+/// Returns the number of column groups and the number of segments of the trace for a batch of
+/// `batch_size` hash chains.
+///
+/// The number of segments is the smallest power of two for which at most [max_batch_width]
+/// groups hold all chains, and the chains are spread over as few groups as possible. When the
+/// groups hold more chains than the batch, the remaining segments of the last group repeat the
+/// last chain of the batch.
+///
+/// # Panics
+/// Panics if `batch_size` is zero.
+pub fn batch_layout<P: AlgebraicPermutation>(batch_size: usize) -> (usize, usize) {
+    assert!(batch_size > 0, "batch must contain at least one seed");
+    let segments = batch_size.div_ceil(max_batch_width::<P>()).next_power_of_two();
+    (batch_size.div_ceil(segments), segments)
+}
+
+/// Returns the length of a trace holding a batch of `batch_size` hash chains of `iterations`
+/// chained hashes each, or `None` if it does not fit into usize.
+pub fn batch_trace_length<P: AlgebraicPermutation>(
+    batch_size: usize,
+    iterations: usize,
+) -> Option<usize> {
+    let (_, segments) = batch_layout::<P>(batch_size);
+    trace_length::<P>(iterations)?.checked_mul(segments)
+}
+
+/// Returns the index of the chain held by the specified segment of the specified column group.
+pub(crate) fn chain_index(
+    batch_size: usize,
+    segments: usize,
+    group: usize,
+    segment: usize,
+) -> usize {
+    (group * segments + segment).min(batch_size - 1)
+}
+
+/// Builds an execution trace computing `iterations` chained hashes for every seed; the chains
+/// are laid out as described by [batch_layout].
+///
+/// # Panics
+/// Panics if `seeds` is empty, if `iterations` is zero, or if the length of the trace does not
+/// fit into usize.
+pub fn build_trace<P: AlgebraicPermutation>(
+    seeds: &[[BaseElement; 2]],
+    iterations: usize,
+) -> TraceTable<BaseElement> {
+    assert!(iterations > 0, "number of iterations must be greater than zero");
+    let (groups, segments) = batch_layout::<P>(seeds.len());
+    let segment_length = trace_length::<P>(iterations).expect("too many iterations");
+    let trace_length = segment_length.checked_mul(segments).expect("too many seeds");
+    let mut trace = TraceTable::new(groups * P::STATE_WIDTH, trace_length);
+
+    // initialize the first state of every chain in the segment
+    let start_segment = |segment: usize, state: &mut [BaseElement]| {
+        for (group, chain) in state.chunks_mut(P::STATE_WIDTH).enumerate() {
+            let seed = seeds[chain_index(seeds.len(), segments, group, segment)];
+            chain[0] = seed[0];
+            chain[1] = seed[1];
+            chain[2..].fill(BaseElement::ZERO);
+        }
+    };
+
+    trace.fill(
+        |state| start_segment(0, state),
+        |step, state| {
+            // execute the transition function of every chain, see [crate::build_trace], and
+            // start the chains of the next segment after the last step of a segment
+            if (step + 1) % segment_length == 0 {
+                start_segment((step + 1) / segment_length, state);
+                return;
+            }
+            for chain in state.chunks_mut(P::STATE_WIDTH) {
+                if (step % P::CYCLE_LENGTH) < P::NUM_ROUNDS {
+                    P::apply_round(chain, step);
+                } else {
+                    chain[2..].fill(BaseElement::ZERO);
+                }
+            }
+        },
+    );
+    trace
+}
+
+// PROVE AND VERIFY
+// ================================================================================================
+
+/// Computes `iterations` chained Rescue hashes for every seed and generates a single STARK
+/// proof attesting to the correctness of all of them.
+pub fn prove_batch(
+    seeds: &[[BaseElement; 2]],
+    iterations: usize,
+    options: ProofOptions,
+) -> Result<(BatchPublicInputs, Proof), Error> {
+    if seeds.is_empty() {
+        return Err(Error::PublicInputMismatch(
+            "batch must contain at least one seed".to_string(),
+        ));
+    }
+    if iterations == 0 {
+        return Err(Error::PublicInputMismatch(
+            "number of iterations must be greater than zero".to_string(),
+        ));
+    }
+    check_trace_length(batch_trace_length::<Rescue4x14>(seeds.len(), iterations), &options)?;
+    check_blowup_factor(&options, Rescue4x14::CONSTRAINT_DEGREE)?;
+
+    let trace = build_trace::<Rescue4x14>(seeds, iterations);
+    let prover = BatchProver::<Rescue4x14>::new(options, seeds.len(), iterations);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace)?;
+    Ok((pub_inputs, proof))
+}
+
+/// Verifies that `proof` attests to all hash chains described by `pub_inputs`, and returns the
+/// security level of the proof.
+pub fn verify_batch(pub_inputs: BatchPublicInputs, proof: Proof) -> Result<SecurityLevel, Error> {
+    verify_batch_with(pub_inputs, proof, &SecurityPolicy::default())
+}
+
+/// Same as [verify_batch], but accepts the proof under the specified security policy.
+pub fn verify_batch_with(
+    pub_inputs: BatchPublicInputs,
+    proof: Proof,
+    policy: &SecurityPolicy,
+) -> Result<SecurityLevel, Error> {
+    let batch_size = pub_inputs.batch_size();
+    let trace_info = proof.trace_info();
+    let (groups, _) = batch_layout::<Rescue4x14>(batch_size);
+    let trace_width = groups * Rescue4x14::STATE_WIDTH;
+    if trace_info.width() != trace_width {
+        return Err(Error::PublicInputMismatch(format!(
+            "expected trace width {trace_width} for {batch_size} chains, but proof trace width \
+             is {}",
+            trace_info.width()
+        )));
+    }
+    if pub_inputs.iterations == 0 {
+        return Err(Error::PublicInputMismatch(
+            "number of iterations must be greater than zero".to_string(),
+        ));
+    }
+    let trace_length = batch_trace_length::<Rescue4x14>(batch_size, pub_inputs.iterations);
+    if trace_length != Some(trace_info.length()) {
+        return Err(Error::PublicInputMismatch(format!(
+            "trace of length {} cannot hold {batch_size} chains of {} iterations",
+            trace_info.length(),
+            pub_inputs.iterations
+        )));
+    }
+    check_blowup_factor(proof.options(), Rescue4x14::CONSTRAINT_DEGREE)?;
+
+    verify_with_policy::<BatchAir, Blake3>(proof, pub_inputs, policy)
+}
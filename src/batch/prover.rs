@@ -0,0 +1,80 @@
+use core::marker::PhantomData;
+
+use winter_air::PartitionOptions;
+use winterfell::{
+    crypto::{DefaultRandomCoin, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    matrix::ColMatrix,
+    AuxRandElements, DefaultConstraintEvaluator, DefaultTraceLde, ProofOptions, Prover,
+    StarkDomain, Trace, TraceInfo, TracePolyTable, TraceTable,
+};
+
+use super::{batch_layout, BatchAir, BatchPublicInputs};
+use crate::{result_step, AlgebraicPermutation, Blake3, Rescue4x14};
+
+pub struct BatchProver<P: AlgebraicPermutation = Rescue4x14> {
+    options: ProofOptions,
+    batch_size: usize,
+    iterations: usize,
+    _permutation: PhantomData<P>,
+}
+
+impl<P: AlgebraicPermutation> BatchProver<P> {
+    /// Returns a prover for `batch_size` hash chains of `iterations` chained hashes each.
+    pub fn new(options: ProofOptions, batch_size: usize, iterations: usize) -> Self {
+        Self { options, batch_size, iterations, _permutation: PhantomData }
+    }
+}
+
+impl<P: AlgebraicPermutation> Prover for BatchProver<P> {
+    type BaseField = BaseElement;
+    type Air = BatchAir<P>;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+    type VC = MerkleTree<Self::HashFn>;
+    type RandomCoin = DefaultRandomCoin<Blake3>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Blake3, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, BatchAir<P>, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BatchPublicInputs {
+        // chain i starts in segment i % segments of column group i / segments
+        let (_, segments) = batch_layout::<P>(self.batch_size);
+        let segment_length = trace.length() / segments;
+        let result_step = result_step::<P>(self.iterations);
+        let chains = (0..self.batch_size)
+            .map(|i| ((i / segments) * P::STATE_WIDTH, (i % segments) * segment_length));
+        BatchPublicInputs {
+            seeds: chains.clone().map(|(c, s)| [trace.get(c, s), trace.get(c + 1, s)]).collect(),
+            results: chains
+                .map(|(c, s)| {
+                    [trace.get(c, s + result_step), trace.get(c + 1, s + result_step)]
+                })
+                .collect(),
+            iterations: self.iterations,
+        }
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_option: PartitionOptions,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain, partition_option)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        air: &'a BatchAir<P>,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: winterfell::ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
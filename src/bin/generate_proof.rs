@@ -1,61 +1,97 @@
-use std::fs::File;
-use std::io::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
 use std::time::Instant;
-use winterfell::{
-    math::{fields::f128::BaseElement, FieldElement}, FieldExtension, ProofOptions, Prover,
+
+use clap::Parser;
+
+use stark_test::cli::{parse_hash_function, HashChainArgs, ProofOptionsArgs};
+use stark_test::hiding::{self, prove_hidden_seed};
+use stark_test::{
+    prove_do_work_bundle, trace_length, Error, HashFunction, ProofBundle, Rescue4x14,
 };
-use stark_test::{build_trace, DoWorkProver};
 
-fn main() {
-    // Start timer
-    let start_time = Instant::now();
+/// Generates a STARK proof for a chain of Rescue hashes.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    #[command(flatten)]
+    chain: HashChainArgs,
+
+    /// Keep the seed private; only the result of the hash chain is made public
+    #[arg(long)]
+    hide_seed: bool,
 
-    let seed = [BaseElement::from(42u8), BaseElement::from(43u8)];
-    let n = 16;
+    /// Make a commitment to the private seed public
+    #[arg(long, requires = "hide_seed")]
+    commit_seed: bool,
 
-    let trace = build_trace(seed);
-    let result = [trace.get(0,n-1), trace.get(1, n-1)];
+    /// Hash function used to commit to the trace: blake3-256, blake3-192 or sha3-256; seeds
+    /// can be hidden only with blake3-256
+    #[arg(
+        long,
+        default_value = "blake3-256",
+        value_parser = parse_hash_function,
+        conflicts_with = "hide_seed",
+    )]
+    hash_fn: HashFunction,
 
-    
+    /// Path of the proof bundle to write
+    #[arg(short, long, default_value = "./artifacts/proof.bin")]
+    output: PathBuf,
 
-    let options = ProofOptions::new(
-        32, // number of queries
-        8,  // blowup factor
-        0,  // grinding factor
-        FieldExtension::None,
-        8,   // FRI folding factor
-        127, // FRI remainder max degree
-    );
+    #[command(flatten)]
+    options: ProofOptionsArgs,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(err) = run(cli) {
+        eprintln!("Error: {err}");
+        process::exit(err.exit_code());
+    }
+}
 
-    let prover = DoWorkProver::new(options);
+fn run(cli: Cli) -> Result<(), Error> {
+    // Start timer
+    let start_time = Instant::now();
 
-    // Write result and proof to files
-    let result_path = "./artifacts/result.txt";
-    let proof_path = "./artifacts/proof.txt";
+    let seed = cli.chain.seed();
+    let iterations = cli.chain.iterations;
 
-    // Ensure the artifacts directory exists
-    std::fs::create_dir_all("./artifacts").unwrap();
+    // The production profile depends on the trace length, which for hidden seeds depends on the
+    // number of queries; the number of queries does not depend on the trace length. Traces whose
+    // length overflows are rejected by the prover
+    let trace_length = trace_length::<Rescue4x14>(iterations).unwrap_or(usize::MAX);
+    let mut options = cli.options.to_proof_options(trace_length);
+    if cli.hide_seed {
+        let blinding_steps = hiding::blinding_steps(&options);
+        let trace_length = hiding::trace_length(iterations, blinding_steps).unwrap_or(usize::MAX);
+        options = cli.options.to_proof_options(trace_length);
+    }
 
-    // Write result to file
-    let mut result_file = File::create(result_path).expect("Unable to create result file");
-    let result_bytes = BaseElement::elements_as_bytes(&result);
-    result_file
-        .write_all(&result_bytes)
-        .expect("Could not write result bytes to file");
+    // Ensure the output directory exists
+    if let Some(dir) = cli.output.parent() {
+        fs::create_dir_all(dir)?;
+    }
 
-    let proof = prover.prove(trace).unwrap();
-    let proof_bytes = proof.to_bytes();
-    // Write proof to file
-    let mut proof_file = File::create(proof_path).expect("Unable to create proof file");
-    proof_file
-        .write_all(&proof_bytes)
-        .expect("Could not write proof bytes to file");
+    // Write public inputs and proof to the bundle file
+    let result = if cli.hide_seed {
+        let (pub_inputs, proof) = prove_hidden_seed(seed, iterations, cli.commit_seed, options)?;
+        ProofBundle::new(pub_inputs, proof).write_to_file(&cli.output)?;
+        pub_inputs.result
+    } else {
+        let bundle = prove_do_work_bundle(seed, iterations, cli.hash_fn, options)?;
+        bundle.write_to_file(&cli.output)?;
+        bundle.pub_inputs.result
+    };
 
     // Calculate elapsed time
     let elapsed = start_time.elapsed();
 
     // Print the time taken and file paths
     println!("Computation completed in: {:.2?}", elapsed);
-    println!("Result written to: {}", result_path);
-    println!("Proof written to: {}", proof_path);
+    println!("Result: [{}, {}]", result[0], result[1]);
+    println!("Proof bundle written to: {}", cli.output.display());
+    Ok(())
 }
@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use clap::Parser;
+use serde_json::json;
+use winterfell::Proof;
+
+use stark_test::json::ProofMetadata;
+use stark_test::{
+    read_bundle_proof, BundleHeader, Error, ProofSize, SecurityLevel, BUNDLE_HEADER_SIZE,
+};
+
+/// Describes a proof bundle: the parameters of its proof, how many bytes each part of the proof
+/// takes, and the security level of the proof.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// Path of the proof bundle to inspect
+    #[arg(short, long, default_value = "./artifacts/proof.bin")]
+    input: PathBuf,
+
+    /// Print the description as a JSON object
+    #[arg(long)]
+    json: bool,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(err) = run(cli) {
+        eprintln!("Error: {err}");
+        process::exit(err.exit_code());
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Error> {
+    let bytes = fs::read(&cli.input)?;
+    let (header, proof) = read_bundle_proof(&bytes)?;
+
+    let metadata = ProofMetadata::from_proof(header.air, header.hash_fn, &proof);
+    let security = SecurityLevel::with_hash_fn(header.hash_fn, &proof);
+    let size = ProofSize::of(&proof);
+    let pub_inputs_size = bytes.len() - BUNDLE_HEADER_SIZE - size.total();
+
+    if cli.json {
+        let info = json!({
+            "metadata": metadata,
+            "unique_queries": proof.num_unique_queries,
+            "fri_layers": proof.fri_proof.num_layers(),
+            "conjectured_security": security.conjectured,
+            "proven_security": security.proven,
+            "size": {
+                "header": BUNDLE_HEADER_SIZE,
+                "public_inputs": pub_inputs_size,
+                "context": size.context,
+                "commitments": size.commitments,
+                "trace_openings": size.trace_openings,
+                "constraint_openings": size.constraint_openings,
+                "ood_frame": size.ood_frame,
+                "fri_layers": size.fri_layers,
+                "fri_remainder": size.fri_remainder,
+                "pow_nonce": size.pow_nonce,
+                "gkr_proof": size.gkr_proof,
+                "total": bytes.len(),
+            },
+        });
+        println!("{info:#}");
+    } else {
+        print_metadata(&header, &metadata, &proof);
+        println!("Security: {security}");
+        println!();
+        print_size(&size, pub_inputs_size, bytes.len());
+    }
+    Ok(())
+}
+
+// REPORTING
+// ================================================================================================
+
+fn print_metadata(header: &BundleHeader, metadata: &ProofMetadata, proof: &Proof) {
+    let options = &metadata.options;
+    let fri_options = options.to_fri_options();
+    println!("AIR: {}", header.air);
+    println!("Hash function: {}", header.hash_fn);
+    println!("Trace: width {}, length {}", metadata.trace_width, metadata.trace_length);
+    println!(
+        "Options: {} queries, blowup factor {}, grinding factor {}, field extension {}",
+        options.num_queries(),
+        options.blowup_factor(),
+        options.grinding_factor(),
+        format!("{:?}", options.field_extension()).to_lowercase(),
+    );
+    println!(
+        "FRI: folding factor {}, remainder max degree {}, number of layers {}",
+        fri_options.folding_factor(),
+        fri_options.remainder_max_degree(),
+        proof.fri_proof.num_layers(),
+    );
+    println!("Unique queries: {} of {}", proof.num_unique_queries, options.num_queries());
+}
+
+fn print_size(size: &ProofSize, pub_inputs_size: usize, total: usize) {
+    println!("Size breakdown:");
+    let parts = [
+        ("header", BUNDLE_HEADER_SIZE),
+        ("public inputs", pub_inputs_size),
+        ("proof context", size.context),
+        ("commitments", size.commitments),
+        ("trace openings", size.trace_openings),
+        ("constraint openings", size.constraint_openings),
+        ("out-of-domain frame", size.ood_frame),
+        ("FRI layers", size.fri_layers),
+        ("FRI remainder", size.fri_remainder),
+        ("proof-of-work nonce", size.pow_nonce),
+        ("GKR proof", size.gkr_proof),
+    ];
+    for (name, bytes) in parts {
+        let share = 100.0 * bytes as f64 / total as f64;
+        println!("  {name:<22}{bytes:>9} B {share:>6.2}%");
+    }
+    println!("  {:<22}{total:>9} B", "total");
+}
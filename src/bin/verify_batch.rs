@@ -0,0 +1,221 @@
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use serde_json::json;
+
+use stark_test::cli::SecurityPolicyArgs;
+use stark_test::{verify_bundle, AirId, Error, SecurityLevel, SecurityPolicy};
+
+/// Verifies many proof bundles in parallel and reports the outcome for each of them.
+///
+/// Exits with code 0 if all bundles verify, and with code 1 if any of them fails.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// Proof bundles to verify; for directories, all `.bin` files in them are verified
+    paths: Vec<PathBuf>,
+
+    /// File listing paths of proof bundles, one per line; relative paths are resolved against
+    /// the directory of the manifest, and empty lines and lines starting with '#' are skipped
+    #[arg(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// Number of worker threads [default: number of CPU cores]
+    #[arg(short, long)]
+    jobs: Option<NonZeroUsize>,
+
+    /// Print results as JSON lines instead of a human-readable summary
+    #[arg(long)]
+    json: bool,
+
+    #[command(flatten)]
+    security: SecurityPolicyArgs,
+}
+
+/// Outcome of verifying a single proof bundle.
+struct Outcome {
+    path: PathBuf,
+    result: Result<(AirId, SecurityLevel), Error>,
+    elapsed: Duration,
+}
+
+/// Exit code reported when at least one of the bundles fails to verify.
+const FAILURE_EXIT_CODE: i32 = 1;
+
+fn main() {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(true) => {},
+        Ok(false) => process::exit(FAILURE_EXIT_CODE),
+        Err(err) => {
+            eprintln!("Error: {err}");
+            process::exit(err.exit_code());
+        },
+    }
+}
+
+/// Returns true if all bundles were verified successfully.
+fn run(cli: Cli) -> Result<bool, Error> {
+    // Start timer
+    let start_time = Instant::now();
+
+    let paths = collect_paths(&cli)?;
+    let jobs = cli.jobs.map_or_else(default_jobs, NonZeroUsize::get).min(paths.len());
+    let outcomes = verify_all(&paths, jobs, &cli.security.to_policy());
+
+    // Calculate elapsed time
+    let elapsed = start_time.elapsed();
+
+    let passed = outcomes.iter().filter(|outcome| outcome.result.is_ok()).count();
+    let failed = outcomes.len() - passed;
+    if cli.json {
+        for outcome in &outcomes {
+            println!("{}", outcome_to_json(outcome));
+        }
+        let summary = json!({
+            "summary": {
+                "total": outcomes.len(),
+                "passed": passed,
+                "failed": failed,
+                "jobs": jobs,
+                "elapsed_ms": elapsed.as_secs_f64() * 1000.0,
+            }
+        });
+        println!("{summary}");
+    } else {
+        for outcome in &outcomes {
+            print_outcome(outcome);
+        }
+        println!();
+        println!("Verified {} bundles: {passed} passed, {failed} failed", outcomes.len());
+        println!("Worker threads: {jobs}");
+        println!("Computation completed in: {:.2?}", elapsed);
+    }
+
+    Ok(failed == 0)
+}
+
+// INPUTS
+// ================================================================================================
+
+/// Returns paths of all bundles listed on the command line and in the manifest.
+fn collect_paths(cli: &Cli) -> Result<Vec<PathBuf>, Error> {
+    let mut paths = Vec::new();
+    for path in &cli.paths {
+        if path.is_dir() {
+            paths.extend(list_bundles(path)?);
+        } else {
+            paths.push(path.clone());
+        }
+    }
+
+    if let Some(manifest) = &cli.manifest {
+        let base = manifest.parent().unwrap_or(Path::new(""));
+        for line in fs::read_to_string(manifest)?.lines() {
+            let line = line.trim();
+            if !line.is_empty() && !line.starts_with('#') {
+                paths.push(base.join(line));
+            }
+        }
+    }
+
+    if paths.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, "no proof bundles to verify").into());
+    }
+    Ok(paths)
+}
+
+/// Returns paths of all `.bin` files in the directory, sorted by name.
+fn list_bundles(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "bin") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+// VERIFICATION
+// ================================================================================================
+
+/// Verifies all bundles using `jobs` worker threads; outcomes are returned in the order of
+/// `paths`.
+fn verify_all(paths: &[PathBuf], jobs: usize, policy: &SecurityPolicy) -> Vec<Outcome> {
+    let next = AtomicUsize::new(0);
+    let outcomes = Mutex::new(Vec::with_capacity(paths.len()));
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(path) = paths.get(index) else {
+                    break;
+                };
+                let outcome = verify_file(path, policy);
+                outcomes.lock().expect("worker thread panicked").push((index, outcome));
+            });
+        }
+    });
+
+    let mut outcomes = outcomes.into_inner().expect("worker thread panicked");
+    outcomes.sort_by_key(|(index, _)| *index);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+fn verify_file(path: &Path, policy: &SecurityPolicy) -> Outcome {
+    let start_time = Instant::now();
+    let result = fs::read(path)
+        .map_err(Error::from)
+        .and_then(|bytes| verify_bundle(&bytes, policy));
+    Outcome { path: path.to_path_buf(), result, elapsed: start_time.elapsed() }
+}
+
+// REPORTING
+// ================================================================================================
+
+fn print_outcome(outcome: &Outcome) {
+    let path = outcome.path.display();
+    match &outcome.result {
+        Ok((air, security)) => {
+            println!("PASS {path} ({air}, {security}) in {:.2?}", outcome.elapsed);
+        },
+        Err(err) => println!("FAIL {path}: {err}"),
+    }
+}
+
+fn outcome_to_json(outcome: &Outcome) -> serde_json::Value {
+    let path = outcome.path.display().to_string();
+    let elapsed_ms = outcome.elapsed.as_secs_f64() * 1000.0;
+    match &outcome.result {
+        Ok((air, security)) => json!({
+            "file": path,
+            "status": "pass",
+            "air": air.to_string(),
+            "conjectured_security": security.conjectured,
+            "proven_security": security.proven,
+            "elapsed_ms": elapsed_ms,
+        }),
+        Err(err) => json!({
+            "file": path,
+            "status": "fail",
+            "error": err.to_string(),
+            "exit_code": err.exit_code(),
+            "elapsed_ms": elapsed_ms,
+        }),
+    }
+}
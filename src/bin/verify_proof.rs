@@ -1,78 +1,126 @@
-use std::fs::File;
-use std::io::Read;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
 use std::time::Instant;
-use winterfell::crypto::MerkleTree;
+
+use clap::Parser;
 use winterfell::math::fields::f128::BaseElement;
-use winterfell::math::FieldElement;
-use winterfell::Proof;
 
-use winterfell::{
-    crypto::DefaultRandomCoin,
-    verify, AcceptableOptions
+use stark_test::cli::{parse_element, SecurityPolicyArgs};
+use stark_test::hiding::{commit_to_seed, verify_hidden_seed_with, HiddenSeedPublicInputs};
+use stark_test::{
+    verify_do_work_bundle, AirId, BundleHeader, Error, ProofBundle, PublicInputs, SecurityLevel,
 };
 
-use stark_test::{PublicInputs, TrainAir, Blake3};
+/// Verifies a STARK proof for a chain of Rescue hashes.
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// Path of the proof bundle to verify
+    #[arg(short, long, default_value = "./artifacts/proof.bin")]
+    input: PathBuf,
+
+    /// Reject the bundle unless the hash chain starts from these two field elements; for bundles
+    /// with a hidden seed, the seed is checked against the seed commitment
+    #[arg(long, num_args = 2, value_names = ["S0", "S1"], value_parser = parse_element)]
+    seed: Option<Vec<BaseElement>>,
+
+    /// Reject the bundle unless it proves this number of chained hashes
+    #[arg(short = 'n', long)]
+    iterations: Option<usize>,
+
+    #[command(flatten)]
+    security: SecurityPolicyArgs,
+}
 
 fn main() {
+    let cli = Cli::parse();
+    if let Err(err) = run(cli) {
+        eprintln!("Error: {err}");
+        process::exit(err.exit_code());
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Error> {
     // Start timer
     let start_time = Instant::now();
 
-    let seed = [BaseElement::from(42u8), BaseElement::from(43u8)];
-
-    let result_path = "./artifacts/result.txt";
-    let proof_path = "./artifacts/proof.txt";
-
-    // Read the result from result.txt
-    let mut result_file = File::open(result_path).expect("Failed to open result file");
-    let mut result_bytes = Vec::new();
-    result_file
-        .read_to_end(&mut result_bytes)
-        .expect("Could not read results");
-
-    // Ensure that `result_bytes` has a length divisible by 16
-    assert!(
-        result_bytes.len() % 16 == 0,
-        "result_bytes length must be divisible by 16"
-    );
-    let result_slice = unsafe {BaseElement::bytes_as_elements(&result_bytes).unwrap()};
-
-    assert!(
-        result_slice.len() == 2,
-        "Expected exactly 2 BaseElement items, found {}",
-        result_slice.len()
-    );
-
-    let result: [BaseElement; 2] = result_slice.try_into().unwrap();
-
-    // Read the proof from proof.txt
-    let mut proof_file = File::open(proof_path).expect("Failed to open proof file");
-    let mut proof_bytes = Vec::new();
-    proof_file
-        .read_to_end(&mut proof_bytes)
-        .expect("Could not read proof");
-    let proof = Proof::from_bytes(&proof_bytes).unwrap();
-
-    let verify_result = verify_do_work(seed, result, proof);
-    println!("Verification outcome: {:?}", verify_result);
+    // The header tells whether the seed of the hash chain is public
+    let bytes = fs::read(&cli.input)?;
+    let security = match BundleHeader::from_bytes(&bytes)?.air {
+        AirId::HiddenSeed => verify_hidden(&cli, &bytes)?,
+        _ => verify_public(&cli, &bytes)?,
+    };
+    println!("Security: {security}");
+    println!("yay! all good!");
+
     // Calculate elapsed time
     let elapsed = start_time.elapsed();
 
     // Print the time taken and file paths
     println!("Computation completed in: {:.2?}", elapsed);
+    Ok(())
 }
 
+/// Verifies a bundle for a hash chain with a public seed.
+fn verify_public(cli: &Cli, bytes: &[u8]) -> Result<SecurityLevel, Error> {
+    let bundle = ProofBundle::<PublicInputs>::from_bytes(bytes)?;
+    let pub_inputs = bundle.pub_inputs;
+
+    if let Some(seed) = &cli.seed {
+        if *seed != pub_inputs.seed {
+            return Err(Error::PublicInputMismatch(format!(
+                "expected seed [{}, {}], but bundle seed is [{}, {}]",
+                seed[0], seed[1], pub_inputs.seed[0], pub_inputs.seed[1]
+            )));
+        }
+    }
+    check_iterations(cli, pub_inputs.iterations)?;
+
+    println!("Seed: [{}, {}]", pub_inputs.seed[0], pub_inputs.seed[1]);
+    println!("Result: [{}, {}]", pub_inputs.result[0], pub_inputs.result[1]);
+    println!("Iterations: {}", pub_inputs.iterations);
+    println!("Hash function: {}", bundle.hash_fn);
+
+    verify_do_work_bundle(bundle, &cli.security.to_policy())
+}
 
-pub fn verify_do_work(seed: [BaseElement; 2], result: [BaseElement; 2], proof: Proof) {
-    // The verifier will accept proofs with parameters which guarantee 95 bits or more of
-    // conjectured security
-    let min_opts = AcceptableOptions::MinConjecturedSecurity(95);
+/// Verifies a bundle for a hash chain with a private seed; an expected seed can be checked only
+/// against the seed commitment.
+fn verify_hidden(cli: &Cli, bytes: &[u8]) -> Result<SecurityLevel, Error> {
+    let bundle = ProofBundle::<HiddenSeedPublicInputs>::from_bytes(bytes)?;
+    let pub_inputs = bundle.pub_inputs;
+
+    if let Some(seed) = &cli.seed {
+        let Some(commitment) = pub_inputs.seed_commitment else {
+            return Err(Error::PublicInputMismatch(
+                "bundle hides the seed and does not commit to it".to_string(),
+            ));
+        };
+        if commit_to_seed([seed[0], seed[1]]) != commitment {
+            return Err(Error::PublicInputMismatch(format!(
+                "bundle seed commitment does not match seed [{}, {}]",
+                seed[0], seed[1]
+            )));
+        }
+    }
+    check_iterations(cli, pub_inputs.iterations)?;
 
-    // The number of steps and options are encoded in the proof itself, so we don't need to
-    // pass them explicitly to the verifier.
-    let pub_inputs = PublicInputs { seed, result };
-    match verify::<TrainAir, Blake3, DefaultRandomCoin<Blake3>, MerkleTree<Blake3>>(proof, pub_inputs, &min_opts) {
-        Ok(_) => println!("yay! all good!"),
-        Err(_) => panic!("something went terribly wrong!"),
+    match pub_inputs.seed_commitment {
+        Some([c0, c1]) => println!("Seed: hidden, commitment [{c0}, {c1}]"),
+        None => println!("Seed: hidden"),
     }
+    println!("Result: [{}, {}]", pub_inputs.result[0], pub_inputs.result[1]);
+    println!("Iterations: {}", pub_inputs.iterations);
+
+    verify_hidden_seed_with(pub_inputs, bundle.proof, &cli.security.to_policy())
 }
 
+fn check_iterations(cli: &Cli, iterations: usize) -> Result<(), Error> {
+    match cli.iterations {
+        Some(expected) if expected != iterations => Err(Error::PublicInputMismatch(format!(
+            "expected {expected} iterations, but bundle proves {iterations}"
+        ))),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,328 @@
+use core::fmt;
+use std::{fs, path::Path};
+
+use winter_utils::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+};
+use winterfell::{
+    crypto::{
+        hashers::{Blake3_192, Blake3_256, Sha3_256},
+        ElementHasher,
+    },
+    math::fields::f128::BaseElement,
+    Proof,
+};
+
+use crate::{Error, PublicInputs};
+
+mod validation;
+use validation::{check_proof_context, check_proof_openings, BundleReader};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Magic bytes which every proof bundle file starts with.
+pub const BUNDLE_MAGIC: [u8; 4] = *b"STKB";
+
+/// Version of the proof bundle format written by this crate.
+pub const BUNDLE_VERSION: u8 = 1;
+
+/// Number of bytes taken by the bundle header: magic bytes, format version, AIR identifier and
+/// hash function identifier.
+pub const BUNDLE_HEADER_SIZE: usize = BUNDLE_MAGIC.len() + 3;
+
+// TYPES AND INTERFACES
+// ================================================================================================
+
+/// Identifies the AIR a proof was generated for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AirId {
+    /// [TrainAir](crate::TrainAir): a chain of 4-wide Rescue hashes.
+    DoWork = 1,
+    /// [SpongeAir](crate::sponge::SpongeAir): a Rescue128 digest of a private message.
+    Sponge = 2,
+    /// [MerkleAir](crate::merkle::MerkleAir): membership of a private leaf in a Merkle tree.
+    Merkle = 3,
+    /// [HiddenSeedAir](crate::hiding::HiddenSeedAir): a chain of 4-wide Rescue hashes starting
+    /// from a private seed.
+    HiddenSeed = 4,
+    /// [BatchAir](crate::batch::BatchAir): many chains of 4-wide Rescue hashes in one trace.
+    Batch = 5,
+}
+
+/// Identifies the hash function used to commit to the trace and constraint evaluations.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum HashFunction {
+    Blake3_256 = 1,
+    Blake3_192 = 2,
+    Sha3_256 = 3,
+}
+
+/// A hash function which can be used to commit to the trace and constraint evaluations of a
+/// proof stored in a bundle.
+pub trait CommitmentHasher: ElementHasher<BaseField = BaseElement> + Send + Sync + 'static {
+    /// Identifier under which the hash function is recorded in the bundle.
+    const HASH_FN: HashFunction;
+}
+
+impl CommitmentHasher for Blake3_256<BaseElement> {
+    const HASH_FN: HashFunction = HashFunction::Blake3_256;
+}
+
+impl CommitmentHasher for Blake3_192<BaseElement> {
+    const HASH_FN: HashFunction = HashFunction::Blake3_192;
+}
+
+impl CommitmentHasher for Sha3_256<BaseElement> {
+    const HASH_FN: HashFunction = HashFunction::Sha3_256;
+}
+
+/// Public inputs which can be stored in a proof bundle.
+pub trait BundlePublicInputs: Serializable + Deserializable {
+    /// AIR which these public inputs belong to.
+    const AIR_ID: AirId;
+}
+
+/// Header of a proof bundle; can be read without knowing which AIR the bundle is for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BundleHeader {
+    pub version: u8,
+    pub air: AirId,
+    pub hash_fn: HashFunction,
+}
+
+/// A self-describing container holding a proof together with everything needed to verify it.
+///
+/// The layout is: magic bytes, format version, AIR identifier, hash function identifier,
+/// serialized public inputs and serialized proof.
+#[derive(Debug, Clone)]
+pub struct ProofBundle<P: BundlePublicInputs = PublicInputs> {
+    pub hash_fn: HashFunction,
+    pub pub_inputs: P,
+    pub proof: Proof,
+}
+
+/// Represents an error returned when a proof bundle is malformed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleError {
+    /// This error occurs when the file does not start with [BUNDLE_MAGIC].
+    InvalidMagic,
+    /// This error occurs when the file was written with an unknown format version.
+    UnsupportedVersion(u8),
+    /// This error occurs when the AIR identifier is unknown.
+    UnknownAir(u8),
+    /// This error occurs when the bundle was generated for a different AIR than expected.
+    AirMismatch { expected: AirId, actual: AirId },
+    /// This error occurs when the hash function identifier is unknown.
+    UnknownHashFunction(u8),
+    /// This error occurs when proofs for the AIR cannot be committed to using the hash function.
+    UnsupportedHashFunction(AirId, HashFunction),
+    /// This error occurs when the file ends before the bundle has been fully read.
+    Truncated,
+    /// This error occurs when there are bytes left over after the bundle has been read.
+    TrailingBytes,
+}
+
+// PROOF BUNDLE IMPLEMENTATION
+// ================================================================================================
+
+impl<P: BundlePublicInputs> ProofBundle<P> {
+    /// Returns a new bundle for a proof generated using Blake3_256 commitments.
+    pub fn new(pub_inputs: P, proof: Proof) -> Self {
+        Self::with_hash_fn(HashFunction::Blake3_256, pub_inputs, proof)
+    }
+
+    /// Returns a new bundle for a proof generated using commitments with the specified hash
+    /// function.
+    pub fn with_hash_fn(hash_fn: HashFunction, pub_inputs: P, proof: Proof) -> Self {
+        Self { hash_fn, pub_inputs, proof }
+    }
+
+    /// Returns the header describing this bundle.
+    pub fn header(&self) -> BundleHeader {
+        BundleHeader {
+            version: BUNDLE_VERSION,
+            air: P::AIR_ID,
+            hash_fn: self.hash_fn,
+        }
+    }
+
+    /// Serializes this bundle into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut target = Vec::new();
+        self.header().write_into(&mut target);
+        self.pub_inputs.write_into(&mut target);
+        self.proof.write_into(&mut target);
+        target
+    }
+
+    /// Parses a bundle from the provided bytes.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes are not a valid bundle for public inputs of type `P`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut source = BundleReader::new(bytes);
+        let header = BundleHeader::read(&mut source)?;
+        if header.air != P::AIR_ID {
+            return Err(BundleError::AirMismatch { expected: P::AIR_ID, actual: header.air }.into());
+        }
+
+        let pub_inputs = P::read_from(&mut source).map_err(map_eof)?;
+
+        // winterfell panics on some malformed proof contexts instead of returning an error; see
+        // the validation module for the checks made on the proof before it reaches winterfell
+        check_proof_context(&mut source.clone()).map_err(map_eof)?;
+        let proof = Proof::read_from(&mut source).map_err(map_eof)?;
+        if source.has_more_bytes() {
+            return Err(BundleError::TrailingBytes.into());
+        }
+        // the verifier parses authentication paths itself and allocates memory for as many nodes
+        // as they claim to hold
+        check_proof_openings(&proof, header.hash_fn)?;
+
+        Ok(Self { hash_fn: header.hash_fn, pub_inputs, proof })
+    }
+
+    /// Writes this bundle into the file at the specified path.
+    pub fn write_to_file<T: AsRef<Path>>(&self, path: T) -> Result<(), Error> {
+        fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    /// Reads a bundle from the file at the specified path.
+    pub fn read_from_file<T: AsRef<Path>>(path: T) -> Result<Self, Error> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+}
+
+// BUNDLE HEADER IMPLEMENTATION
+// ================================================================================================
+
+impl BundleHeader {
+    /// Parses the header from the start of the provided bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::read(&mut SliceReader::new(bytes))
+    }
+
+    fn read<R: ByteReader>(source: &mut R) -> Result<Self, Error> {
+        let magic: [u8; 4] = source.read_array().map_err(map_eof)?;
+        if magic != BUNDLE_MAGIC {
+            return Err(BundleError::InvalidMagic.into());
+        }
+
+        let version = source.read_u8().map_err(map_eof)?;
+        if version != BUNDLE_VERSION {
+            return Err(BundleError::UnsupportedVersion(version).into());
+        }
+
+        let air = AirId::try_from(source.read_u8().map_err(map_eof)?)?;
+        let hash_fn = HashFunction::try_from(source.read_u8().map_err(map_eof)?)?;
+        Ok(Self { version, air, hash_fn })
+    }
+
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&BUNDLE_MAGIC);
+        target.write_u8(self.version);
+        target.write_u8(self.air as u8);
+        target.write_u8(self.hash_fn as u8);
+    }
+}
+
+// IDENTIFIER CONVERSIONS
+// ================================================================================================
+
+impl TryFrom<u8> for AirId {
+    type Error = BundleError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::DoWork),
+            2 => Ok(Self::Sponge),
+            3 => Ok(Self::Merkle),
+            4 => Ok(Self::HiddenSeed),
+            5 => Ok(Self::Batch),
+            _ => Err(BundleError::UnknownAir(value)),
+        }
+    }
+}
+
+impl HashFunction {
+    /// Returns the number of bytes in a serialized digest of this hash function.
+    pub fn digest_size(&self) -> usize {
+        match self {
+            Self::Blake3_256 | Self::Sha3_256 => 32,
+            Self::Blake3_192 => 24,
+        }
+    }
+}
+
+impl TryFrom<u8> for HashFunction {
+    type Error = BundleError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::Blake3_256),
+            2 => Ok(Self::Blake3_192),
+            3 => Ok(Self::Sha3_256),
+            _ => Err(BundleError::UnknownHashFunction(value)),
+        }
+    }
+}
+
+impl fmt::Display for AirId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DoWork => write!(f, "do-work"),
+            Self::Sponge => write!(f, "sponge"),
+            Self::Merkle => write!(f, "merkle"),
+            Self::HiddenSeed => write!(f, "hidden-seed"),
+            Self::Batch => write!(f, "batch"),
+        }
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Blake3_256 => write!(f, "blake3-256"),
+            Self::Blake3_192 => write!(f, "blake3-192"),
+            Self::Sha3_256 => write!(f, "sha3-256"),
+        }
+    }
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => write!(f, "file is not a proof bundle"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "bundle format version {version} is not supported")
+            }
+            Self::UnknownAir(id) => write!(f, "unknown AIR identifier {id}"),
+            Self::AirMismatch { expected, actual } => {
+                write!(f, "expected a bundle for {expected} AIR, but found one for {actual} AIR")
+            }
+            Self::UnknownHashFunction(id) => write!(f, "unknown hash function identifier {id}"),
+            Self::UnsupportedHashFunction(air, hash_fn) => {
+                write!(f, "{air} AIR does not support {hash_fn} commitments")
+            }
+            Self::Truncated => write!(f, "bundle is truncated"),
+            Self::TrailingBytes => write!(f, "bundle has trailing bytes"),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reports running out of input as a truncated bundle; other errors are passed through.
+fn map_eof(err: DeserializationError) -> Error {
+    match err {
+        DeserializationError::UnexpectedEOF => BundleError::Truncated.into(),
+        err => err.into(),
+    }
+}
@@ -0,0 +1,253 @@
+//! Checks made on a serialized proof before it is handed to winterfell.
+//!
+//! winterfell 0.10.1 trusts some of the values it deserializes, and panics, aborts on a failed
+//! allocation, or divides by zero when they are malformed. The checks in this module reject such
+//! values with a [DeserializationError] instead; each of them names the winterfell code it
+//! guards. The checks mirror the serialization layout of that exact version, which is why the
+//! winterfell crates are pinned in `Cargo.toml`; they must be revisited whenever the pin is
+//! bumped.
+
+use winter_utils::{ByteReader, Deserializable, DeserializationError, Serializable};
+use winterfell::{
+    math::{fields::f128::BaseElement, StarkField},
+    FieldExtension, Proof,
+};
+
+use super::HashFunction;
+use crate::{validate_proof_options, Error};
+
+// BUNDLE READER
+// ================================================================================================
+
+/// Reads a bundle from a slice of bytes.
+///
+/// Unlike [SliceReader](winter_utils::SliceReader), this reader fails instead of allocating
+/// memory for more elements than there are bytes left, so that a corrupted length prefix cannot
+/// exhaust memory. This guards `Vec::with_capacity` in the default implementation of
+/// [ByteReader::read_many], which winterfell uses for the trace queries and FRI layers of a
+/// proof.
+#[derive(Clone)]
+pub(super) struct BundleReader<'a> {
+    source: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BundleReader<'a> {
+    pub(super) fn new(source: &'a [u8]) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    fn remaining(&self) -> usize {
+        self.source.len() - self.pos
+    }
+}
+
+impl ByteReader for BundleReader<'_> {
+    fn read_u8(&mut self) -> Result<u8, DeserializationError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn peek_u8(&self) -> Result<u8, DeserializationError> {
+        self.check_eor(1)?;
+        Ok(self.source[self.pos])
+    }
+
+    fn read_slice(&mut self, len: usize) -> Result<&[u8], DeserializationError> {
+        self.check_eor(len)?;
+        let result = &self.source[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(result)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        let mut result = [0_u8; N];
+        result.copy_from_slice(self.read_slice(N)?);
+        Ok(result)
+    }
+
+    fn check_eor(&self, num_bytes: usize) -> Result<(), DeserializationError> {
+        if num_bytes > self.remaining() {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+        Ok(())
+    }
+
+    fn has_more_bytes(&self) -> bool {
+        self.remaining() > 0
+    }
+
+    fn read_many<D: Deserializable>(
+        &mut self,
+        num_elements: usize,
+    ) -> Result<Vec<D>, DeserializationError> {
+        // every element of a bundle takes at least one byte
+        self.check_eor(num_elements)?;
+        let mut result = Vec::with_capacity(num_elements);
+        for _ in 0..num_elements {
+            result.push(D::read_from(self)?);
+        }
+        Ok(result)
+    }
+}
+
+// PROOF CHECKS
+// ================================================================================================
+
+/// Checks the values in the proof context which winterfell does not validate before using them
+/// to construct the trace info and proof options.
+///
+/// This prevents the following panics in `Context::read_from`:
+/// - `TraceInfo::new_multi_segment` asserts that an empty auxiliary segment requires no random
+///   elements, which `TraceInfo::read_from` does not check;
+/// - `TraceInfo::read_from` computes the trace length as `2_usize.pow(log_trace_length)`, which
+///   overflows for lengths of `2^64` and more;
+/// - `ProofOptions::read_from` passes the options to `ProofOptions::new` and
+///   `ProofOptions::with_partitions`, which assert their bounds instead of returning errors;
+///   partition options other than the default are rejected altogether, since changing them
+///   does not always invalidate the proof.
+///
+/// It also prevents overflows in `Proof::security_level`, which the verifier calls before
+/// checking the field modulus: the level is computed from the number of bits in the modulus,
+/// less the log2 of the LDE domain size, computed as the trace length times the blowup factor.
+/// Finally, the LDE domain must be a subgroup of the field, or `StarkField::get_root_of_unity`
+/// panics when the verifier builds it.
+pub(super) fn check_proof_context<R: ByteReader>(
+    source: &mut R,
+) -> Result<(), DeserializationError> {
+    // trace info: segment widths, number of auxiliary random elements, log2 of the trace length
+    // and trace metadata
+    let _main_segment_width = source.read_u8()?;
+    let aux_segment_width = source.read_u8()?;
+    let num_aux_segment_rands = source.read_u8()?;
+    if aux_segment_width == 0 && num_aux_segment_rands != 0 {
+        return Err(DeserializationError::InvalidValue(
+            "an empty auxiliary trace segment cannot require random elements".to_string(),
+        ));
+    }
+    let log_trace_length = source.read_u8()?;
+    if u32::from(log_trace_length) >= usize::BITS {
+        return Err(DeserializationError::InvalidValue(format!(
+            "trace length of 2^{log_trace_length} is not supported"
+        )));
+    }
+    let num_meta_bytes = source.read_u16()? as usize;
+    source.read_slice(num_meta_bytes)?;
+
+    // field modulus; all AIRs of this crate are defined over the f128 field
+    let num_modulus_bytes = source.read_u8()? as usize;
+    if source.read_slice(num_modulus_bytes)? != BaseElement::get_modulus_le_bytes() {
+        return Err(DeserializationError::InvalidValue(
+            "proof is not defined over the f128 field".to_string(),
+        ));
+    }
+
+    // proof options followed by partition options
+    let num_queries = source.read_u8()? as usize;
+    let blowup_factor = source.read_u8()? as usize;
+    let grinding_factor = source.read_u8()? as u32;
+    let field_extension = FieldExtension::read_from(source)?;
+    let folding_factor = source.read_u8()? as usize;
+    let remainder_degree = source.read_u8()? as usize;
+    validate_proof_options(
+        num_queries,
+        blowup_factor,
+        grinding_factor,
+        field_extension,
+        folding_factor,
+        remainder_degree,
+    )
+    .map_err(DeserializationError::InvalidValue)?;
+    if u32::from(log_trace_length) + blowup_factor.ilog2() > BaseElement::TWO_ADICITY {
+        return Err(DeserializationError::InvalidValue(format!(
+            "LDE domain of 2^{log_trace_length} * {blowup_factor} steps is larger than the \
+             largest multiplicative subgroup of the f128 field"
+        )));
+    }
+
+    // partition options are not drawn into the public coin, and many of them describe the same
+    // commitments; proofs generated by this crate always use the default options
+    let num_partitions = source.read_u8()?;
+    let min_partition_size = source.read_u8()?;
+    if (num_partitions, min_partition_size) != (1, 1) {
+        return Err(DeserializationError::InvalidValue(format!(
+            "unsupported partition options: {num_partitions} partitions of at least \
+             {min_partition_size} columns"
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that the proof holds openings for at least one query, that the Merkle authentication
+/// paths of all queried trace values, constraint evaluations and FRI layers hold as many nodes as
+/// they claim to and are shallow enough to address a usize domain, and that the number of FRI
+/// partitions fits into a usize.
+///
+/// The verifier parses authentication paths itself, after the proof has been read, so these
+/// values cannot be checked while reading the bundle. This prevents:
+/// - a panic in `Queries::parse`, which asserts that the number of unique queries recorded in
+///   the proof is not zero;
+/// - failed allocations in `BatchMerkleProof::read_from`, which reserves memory for as many node
+///   vectors as the path claims to hold; it is called by `Queries::parse` and
+///   `FriProofLayer::parse`;
+/// - a panic in `MerkleTree::get_multiproof_domain_len`, which computes `1 << depth`; in release
+///   builds the shift wraps around;
+/// - a panic in `FriProof::num_partitions`, which computes `2_usize.pow(num_partitions)`; in
+///   release builds the power wraps to zero and the FRI verifier divides by it.
+pub(super) fn check_proof_openings(proof: &Proof, hash_fn: HashFunction) -> Result<(), Error> {
+    let num_queries = proof.options().num_queries();
+    if !(1..=num_queries).contains(&usize::from(proof.num_unique_queries)) {
+        return Err(DeserializationError::InvalidValue(format!(
+            "{} unique queries cannot be drawn from {num_queries} queries",
+            proof.num_unique_queries
+        ))
+        .into());
+    }
+
+    for queries in proof.trace_queries.iter().chain([&proof.constraint_queries]) {
+        let bytes = queries.to_bytes();
+        let mut source = BundleReader::new(&bytes);
+        let _values = Vec::<u8>::read_from(&mut source)?;
+        check_batch_merkle_proof(&Vec::<u8>::read_from(&mut source)?, hash_fn)?;
+    }
+
+    let bytes = proof.fri_proof.to_bytes();
+    let mut source = BundleReader::new(&bytes);
+    for _ in 0..source.read_u8()? {
+        let num_value_bytes = source.read_u32()? as usize;
+        source.read_slice(num_value_bytes)?;
+        let num_path_bytes = source.read_u32()? as usize;
+        check_batch_merkle_proof(source.read_slice(num_path_bytes)?, hash_fn)?;
+    }
+    let num_remainder_bytes = source.read_u16()? as usize;
+    source.read_slice(num_remainder_bytes)?;
+    let log_num_partitions = source.read_u8()?;
+    if u32::from(log_num_partitions) >= usize::BITS {
+        return Err(DeserializationError::InvalidValue(format!(
+            "FRI layers cannot be split into 2^{log_num_partitions} partitions"
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Checks that the depth of a serialized batch Merkle proof fits into a usize domain, and that
+/// its node counts match the nodes it holds.
+fn check_batch_merkle_proof(bytes: &[u8], hash_fn: HashFunction) -> Result<(), Error> {
+    let invalid = || DeserializationError::InvalidValue("malformed Merkle proof".to_string());
+    let mut source = BundleReader::new(bytes);
+    if u32::from(source.read_u8()?) >= usize::BITS {
+        return Err(invalid().into());
+    }
+    let num_node_vectors = source.read_usize()?;
+    source.check_eor(num_node_vectors).map_err(|_| invalid())?;
+    for _ in 0..num_node_vectors {
+        let num_nodes = source.read_usize()?;
+        let num_node_bytes =
+            num_nodes.checked_mul(hash_fn.digest_size()).ok_or_else(invalid)?;
+        source.read_slice(num_node_bytes).map_err(|_| invalid())?;
+    }
+    if source.has_more_bytes() {
+        return Err(invalid().into());
+    }
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use clap::Args;
+use winterfell::{math::fields::f128::BaseElement, FieldExtension, ProofOptions};
+
+pub use crate::parse::{parse_element, parse_field_extension, parse_hash_function};
+
+use crate::options::{
+    check_blowup_factor, check_folding_factor, check_grinding_factor, check_num_queries,
+    check_remainder_degree,
+};
+use crate::{
+    production_options, AlgebraicPermutation, Rescue4x14, SecurityPolicy,
+    DEFAULT_MIN_SECURITY_LEVEL,
+};
+
+// ARGUMENT GROUPS
+// ================================================================================================
+
+/// Command-line arguments describing the hash chain being proven.
+#[derive(Debug, Clone, Args)]
+pub struct HashChainArgs {
+    /// Two field elements the hash chain starts from (decimal or 0x-prefixed hex)
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["S0", "S1"],
+        default_values = ["42", "43"],
+        value_parser = parse_element,
+    )]
+    pub seed: Vec<BaseElement>,
+
+    /// Number of chained Rescue hashes
+    #[arg(short = 'n', long, default_value_t = 16, value_parser = parse_iterations)]
+    pub iterations: usize,
+}
+
+impl HashChainArgs {
+    /// Returns the seed as an array of two field elements.
+    pub fn seed(&self) -> [BaseElement; 2] {
+        [self.seed[0], self.seed[1]]
+    }
+}
+
+/// Command-line arguments describing STARK protocol parameters.
+#[derive(Debug, Clone, Args)]
+pub struct ProofOptionsArgs {
+    /// Use the production profile targeting 128 bits of conjectured security; the field
+    /// extension is chosen automatically based on the trace length
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "num_queries",
+            "blowup_factor",
+            "grinding_factor",
+            "field_extension",
+            "folding_factor",
+            "remainder_degree",
+        ],
+    )]
+    pub production: bool,
+
+    /// Number of queries
+    #[arg(short = 'q', long, default_value_t = 32, value_parser = parse_num_queries)]
+    pub num_queries: usize,
+
+    /// Blowup factor; must be a power of two between 4 and 128
+    #[arg(short = 'b', long, default_value_t = 8, value_parser = parse_blowup_factor)]
+    pub blowup_factor: usize,
+
+    /// Number of proof-of-work (grinding) bits; at most 32
+    #[arg(short = 'g', long, default_value_t = 0, value_parser = parse_grinding_factor)]
+    pub grinding_factor: u32,
+
+    /// Field extension used for the composition polynomial: none or quadratic; cubic is not
+    /// supported for the f128 field
+    #[arg(short = 'e', long, default_value = "none", value_parser = parse_field_extension)]
+    pub field_extension: FieldExtension,
+
+    /// FRI folding factor; must be 2, 4, 8 or 16
+    #[arg(short = 'f', long, default_value_t = 8, value_parser = parse_folding_factor)]
+    pub folding_factor: usize,
+
+    /// Maximum degree of the FRI remainder polynomial; must be one less than a power of two
+    /// and at most 255
+    #[arg(short = 'r', long, default_value_t = 127, value_parser = parse_remainder_degree)]
+    pub remainder_degree: usize,
+}
+
+impl ProofOptionsArgs {
+    /// Returns proof options assembled from the parsed arguments; `trace_length` is used only
+    /// by the production profile.
+    pub fn to_proof_options(&self, trace_length: usize) -> ProofOptions {
+        if self.production {
+            return production_options(trace_length);
+        }
+        ProofOptions::new(
+            self.num_queries,
+            self.blowup_factor,
+            self.grinding_factor,
+            self.field_extension,
+            self.folding_factor,
+            self.remainder_degree,
+        )
+    }
+}
+
+/// Command-line arguments describing which proofs the verifier accepts.
+#[derive(Debug, Clone, Args)]
+pub struct SecurityPolicyArgs {
+    /// Minimum conjectured security level in bits [default: 95]
+    #[arg(long, value_name = "BITS")]
+    pub min_conjectured_security: Option<u32>,
+
+    /// Minimum proven security level in bits
+    #[arg(long, value_name = "BITS", conflicts_with = "min_conjectured_security")]
+    pub min_proven_security: Option<u32>,
+
+    /// Accept only proofs generated with these options, given as comma-separated number of
+    /// queries, blowup factor, grinding factor, field extension, FRI folding factor and FRI
+    /// remainder degree, e.g. 32,8,0,none,8,127; can be repeated
+    #[arg(
+        long = "allow-options",
+        value_name = "OPTIONS",
+        value_parser = parse_proof_options,
+        conflicts_with_all = ["min_conjectured_security", "min_proven_security"],
+    )]
+    pub allowed_options: Vec<ProofOptions>,
+}
+
+impl SecurityPolicyArgs {
+    /// Returns the security policy described by the parsed arguments.
+    pub fn to_policy(&self) -> SecurityPolicy {
+        if !self.allowed_options.is_empty() {
+            SecurityPolicy::AllowList(self.allowed_options.clone())
+        } else if let Some(bits) = self.min_proven_security {
+            SecurityPolicy::MinProven(bits)
+        } else {
+            SecurityPolicy::MinConjectured(
+                self.min_conjectured_security.unwrap_or(DEFAULT_MIN_SECURITY_LEVEL),
+            )
+        }
+    }
+}
+
+// VALUE PARSERS
+// ================================================================================================
+
+/// Parses proof options from their comma-separated values in the order accepted by
+/// [ProofOptions::new].
+pub fn parse_proof_options(value: &str) -> Result<ProofOptions, String> {
+    let parts = value.split(',').map(str::trim).collect::<Vec<_>>();
+    let [num_queries, blowup_factor, grinding_factor, field_extension, folding_factor, remainder] =
+        parts[..]
+    else {
+        return Err(format!("'{value}' does not consist of 6 comma-separated values"));
+    };
+
+    Ok(ProofOptions::new(
+        parse_num_queries(num_queries)?,
+        parse_blowup_factor(blowup_factor)?,
+        parse_grinding_factor(grinding_factor)?,
+        parse_field_extension(field_extension)?,
+        parse_folding_factor(folding_factor)?,
+        parse_remainder_degree(remainder)?,
+    ))
+}
+
+fn parse_iterations(value: &str) -> Result<usize, String> {
+    let iterations = parse_usize(value)?;
+    if iterations == 0 {
+        return Err("number of iterations must be greater than zero".to_string());
+    }
+    Ok(iterations)
+}
+
+fn parse_num_queries(value: &str) -> Result<usize, String> {
+    check_num_queries(parse_usize(value)?)
+}
+
+fn parse_blowup_factor(value: &str) -> Result<usize, String> {
+    // the hash chain constraints, which have the lowest degree of all AIRs, need a blowup factor
+    // of at least 4
+    let blowup_factor = check_blowup_factor(parse_usize(value)?)?;
+    let min_blowup_factor = Rescue4x14::CONSTRAINT_DEGREE.next_power_of_two();
+    if blowup_factor < min_blowup_factor {
+        return Err(format!(
+            "blowup factor must be at least {min_blowup_factor} for the constraints of any AIR, \
+             but was {blowup_factor}"
+        ));
+    }
+    Ok(blowup_factor)
+}
+
+fn parse_grinding_factor(value: &str) -> Result<u32, String> {
+    let grinding_factor = value.parse::<u32>().map_err(|err| format!("'{value}': {err}"))?;
+    check_grinding_factor(grinding_factor)
+}
+
+fn parse_folding_factor(value: &str) -> Result<usize, String> {
+    check_folding_factor(parse_usize(value)?)
+}
+
+fn parse_remainder_degree(value: &str) -> Result<usize, String> {
+    check_remainder_degree(parse_usize(value)?)
+}
+
+fn parse_usize(value: &str) -> Result<usize, String> {
+    value.parse::<usize>().map_err(|err| format!("'{value}': {err}"))
+}
@@ -0,0 +1,157 @@
+use core::fmt;
+
+use winterfell::{
+    math::{fft, get_power_series_with_offset, polynom, FieldElement, StarkField},
+    Air, EvaluationFrame, Prover, Trace, TraceTable,
+};
+
+use super::{check_constraints, ConstraintError};
+
+/// Public inputs of the AIR proven by `P`.
+type PublicInputsOf<P> = <<P as Prover>::Air as Air>::PublicInputs;
+
+// MOCK PROVER
+// ================================================================================================
+
+/// Runs the checks of [Prover::prove] which depend only on the trace, and returns the public
+/// inputs which a proof for the trace would attest to.
+///
+/// The AIR of `prover` is instantiated for the trace, then the trace is checked against all
+/// transition constraints and assertions using [check_constraints], and the degrees of the
+/// transition constraints are checked using [check_transition_degrees]. Unlike the prover, the
+/// mock prover does not extend the trace, commit to it, or run FRI, so it takes a fraction of the
+/// time of generating a proof; and it reports where the trace goes wrong.
+pub fn mock_prove<P>(
+    prover: &P,
+    trace: &TraceTable<P::BaseField>,
+) -> Result<PublicInputsOf<P>, MockProofError<P::BaseField>>
+where
+    P: Prover<Trace = TraceTable<<P as Prover>::BaseField>>,
+{
+    let options = prover.options().clone();
+    let air = P::Air::new(trace.info().clone(), prover.get_pub_inputs(trace), options);
+    check_constraints(&air, trace)?;
+    check_transition_degrees(&air, trace)?;
+    Ok(prover.get_pub_inputs(trace))
+}
+
+/// Checks that the transition constraints of the AIR, evaluated over the trace, have the
+/// degrees declared by their [TransitionConstraintDegree](winterfell::TransitionConstraintDegree)
+/// descriptors.
+///
+/// Trace columns are interpolated and evaluated over the constraint evaluation domain, and the
+/// degree of every constraint is measured after dividing out the transition divisor; this is
+/// the check the prover runs when built with debug assertions. The trace must satisfy the
+/// transition constraints, otherwise the divided evaluations are not a polynomial.
+pub fn check_transition_degrees<A: Air>(
+    air: &A,
+    trace: &TraceTable<A::BaseField>,
+) -> Result<(), MockProofError<A::BaseField>> {
+    let trace_length = trace.length();
+    let ce_blowup_factor = air.ce_blowup_factor();
+    let ce_domain_size = air.ce_domain_size();
+
+    // the coefficients do not affect the degrees or the divisor
+    let num_constraints = air.context().num_main_transition_constraints();
+    let constraints = air.get_transition_constraints(&vec![A::BaseField::ONE; num_constraints]);
+    let divisor = constraints.divisor();
+    let expected: Vec<usize> = constraints
+        .main_constraint_degrees()
+        .iter()
+        .map(|degree| degree.get_evaluation_degree(trace_length) - divisor.degree())
+        .collect();
+
+    // evaluate trace columns over the constraint evaluation domain
+    let twiddles = fft::get_twiddles::<A::BaseField>(trace_length);
+    let inv_twiddles = fft::get_inv_twiddles::<A::BaseField>(trace_length);
+    let columns: Vec<Vec<A::BaseField>> = (0..trace.main_trace_width())
+        .map(|column| {
+            let mut poly = trace.get_column(column).to_vec();
+            fft::interpolate_poly(&mut poly, &inv_twiddles);
+            fft::evaluate_poly_with_offset(&poly, &twiddles, air.domain_offset(), ce_blowup_factor)
+        })
+        .collect();
+
+    // evaluate the constraints divided by the divisor at every point of the domain; the next row
+    // of a point is the point shifted by one step of the trace domain
+    let g = A::BaseField::get_root_of_unity(ce_domain_size.ilog2());
+    let domain = get_power_series_with_offset(g, air.domain_offset(), ce_domain_size);
+    let periodic_polys = air.get_periodic_column_polys();
+    let mut frame = EvaluationFrame::new(trace.main_trace_width());
+    let mut periodic_values = vec![A::BaseField::ZERO; periodic_polys.len()];
+    let mut values = vec![A::BaseField::ZERO; num_constraints];
+    let mut evaluations = vec![vec![A::BaseField::ZERO; ce_domain_size]; num_constraints];
+    for (i, &x) in domain.iter().enumerate() {
+        for (column, values) in columns.iter().enumerate() {
+            frame.current_mut()[column] = values[i];
+            frame.next_mut()[column] = values[(i + ce_blowup_factor) % ce_domain_size];
+        }
+        for (value, poly) in periodic_values.iter_mut().zip(&periodic_polys) {
+            let num_cycles = (trace_length / poly.len()) as u64;
+            *value = polynom::eval(poly, x.exp(num_cycles.into()));
+        }
+
+        values.fill(A::BaseField::ZERO);
+        air.evaluate_transition(&frame, &periodic_values, &mut values);
+        let divisor_value = divisor.evaluate_at(x);
+        for (evaluations, &value) in evaluations.iter_mut().zip(&values) {
+            evaluations[i] = value / divisor_value;
+        }
+    }
+
+    let inv_twiddles = fft::get_inv_twiddles::<A::BaseField>(ce_domain_size);
+    let actual: Vec<usize> = evaluations
+        .into_iter()
+        .map(|mut evaluations| {
+            fft::interpolate_poly(&mut evaluations, &inv_twiddles);
+            polynom::degree_of(&evaluations)
+        })
+        .collect();
+
+    if expected != actual {
+        return Err(MockProofError::DegreeMismatch { expected, actual });
+    }
+    Ok(())
+}
+
+// MOCK PROOF ERROR
+// ================================================================================================
+
+/// Represents an error returned when the mock prover finds a trace or an AIR for which
+/// [Prover::prove] would not generate a valid proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockProofError<E: StarkField> {
+    /// The trace does not satisfy the transition constraints or assertions of the AIR.
+    Constraint(ConstraintError<E>),
+    /// The degrees of the transition constraints, after dividing out the transition divisor,
+    /// differ from the degrees the AIR declares for them.
+    DegreeMismatch { expected: Vec<usize>, actual: Vec<usize> },
+}
+
+impl<E: StarkField> From<ConstraintError<E>> for MockProofError<E> {
+    fn from(err: ConstraintError<E>) -> Self {
+        Self::Constraint(err)
+    }
+}
+
+impl<E: StarkField> fmt::Display for MockProofError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Constraint(err) => write!(f, "{err}"),
+            Self::DegreeMismatch { expected, actual } => {
+                let mismatches: Vec<usize> = (0..expected.len())
+                    .filter(|&constraint| expected[constraint] != actual[constraint])
+                    .collect();
+                writeln!(
+                    f,
+                    "degrees of transition constraints {mismatches:?} differ from their declared \
+                     degrees:"
+                )?;
+                writeln!(f, "expected: {expected:>3?}")?;
+                write!(f, "actual:   {actual:>3?}")
+            },
+        }
+    }
+}
+
+impl<E: StarkField> std::error::Error for MockProofError<E> {}
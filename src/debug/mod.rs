@@ -0,0 +1,218 @@
+//! Host-side tools for debugging execution traces against an AIR.
+//!
+//! When a trace does not satisfy the constraints of an AIR, the prover fails with an error which
+//! does not say where the trace goes wrong. The tools in this module evaluate the constraints
+//! directly over the trace and point at the offending rows. Traces can also be exported to CSV
+//! or JSON for inspection, and hand-edited traces imported back.
+//!
+//! Conversely, a valid trace can be searched for cells whose values the constraints do not pin
+//! down, which points at assertions or constraints missing from an AIR. While an AIR is being
+//! developed, the mock prover runs all checks of the prover which do not need a proof.
+
+use core::fmt;
+
+use winterfell::{
+    math::{FieldElement, StarkField},
+    Air, EvaluationFrame, Trace, TraceTable,
+};
+
+use crate::utils::format_trace_row;
+
+mod mock;
+pub use mock::{check_transition_degrees, mock_prove, MockProofError};
+
+mod trace_io;
+pub use trace_io::{NamedTrace, TraceFormatError};
+
+mod under_constrained;
+pub use under_constrained::{find_under_constrained_cells, CellFreedom, UnderConstrainedCell};
+
+// CONSTRAINT CHECKER
+// ================================================================================================
+
+/// Checks that the trace satisfies all transition constraints and assertions of the AIR.
+///
+/// Transition constraints are evaluated on every pair of consecutive rows to which they apply,
+/// with the periodic column values for the current step. If the trace is invalid, the failure at
+/// the earliest step is returned; on the same step, a failed assertion is reported before a failed
+/// transition constraint.
+pub fn check_constraints<A: Air>(
+    air: &A,
+    trace: &TraceTable<A::BaseField>,
+) -> Result<(), ConstraintError<A::BaseField>> {
+    let expected = air.trace_info();
+    if trace.main_trace_width() != expected.main_trace_width()
+        || trace.length() != expected.length()
+    {
+        return Err(ConstraintError::TraceShapeMismatch {
+            expected: (expected.main_trace_width(), expected.length()),
+            actual: (trace.main_trace_width(), trace.length()),
+        });
+    }
+
+    let assertion_error = find_failed_assertion(air, trace);
+    let transition_error = find_failed_transition(air, trace);
+    match (assertion_error, transition_error) {
+        (None, None) => Ok(()),
+        (Some(err), None) | (None, Some(err)) => Err(err),
+        (Some(assertion), Some(transition)) => {
+            if transition.step() < assertion.step() {
+                Err(transition)
+            } else {
+                Err(assertion)
+            }
+        },
+    }
+}
+
+/// Returns the failed assertion with the smallest step, if any.
+fn find_failed_assertion<A: Air>(
+    air: &A,
+    trace: &TraceTable<A::BaseField>,
+) -> Option<ConstraintError<A::BaseField>> {
+    let mut failure: Option<ConstraintError<A::BaseField>> = None;
+    for assertion in air.get_assertions() {
+        let column = assertion.column();
+        assertion.apply(trace.length(), |step, expected| {
+            let actual = trace.get(column, step);
+            let is_earliest = failure.as_ref().is_none_or(|failure| step < failure.step());
+            if actual != expected && is_earliest {
+                failure = Some(ConstraintError::Assertion {
+                    step,
+                    column,
+                    expected,
+                    actual,
+                    row: read_row(trace, step),
+                });
+            }
+        });
+    }
+    failure
+}
+
+/// Returns the first transition constraint which does not evaluate to zero, if any.
+fn find_failed_transition<A: Air>(
+    air: &A,
+    trace: &TraceTable<A::BaseField>,
+) -> Option<ConstraintError<A::BaseField>> {
+    let mut evaluator = TransitionEvaluator::new(air);
+    for step in 0..evaluator.num_steps {
+        if let Some(constraint) = evaluator.find_failed_constraint(trace, step) {
+            let (frame, evaluations) = (&evaluator.frame, &evaluator.evaluations);
+            return Some(ConstraintError::Transition {
+                step,
+                constraint,
+                value: evaluations[constraint],
+                current: frame.current().to_vec(),
+                next: frame.next().to_vec(),
+            });
+        }
+    }
+    None
+}
+
+/// Evaluates the transition constraints of an AIR over pairs of consecutive trace rows.
+struct TransitionEvaluator<'a, A: Air> {
+    air: &'a A,
+    periodic_columns: Vec<Vec<A::BaseField>>,
+    /// Number of steps at which transition constraints apply.
+    num_steps: usize,
+    frame: EvaluationFrame<A::BaseField>,
+    periodic_values: Vec<A::BaseField>,
+    evaluations: Vec<A::BaseField>,
+}
+
+impl<'a, A: Air> TransitionEvaluator<'a, A> {
+    fn new(air: &'a A) -> Self {
+        let periodic_columns = air.get_periodic_column_values();
+        let trace_info = air.trace_info();
+        Self {
+            air,
+            num_steps: trace_info.length() - air.context().num_transition_exemptions(),
+            frame: EvaluationFrame::new(trace_info.main_trace_width()),
+            periodic_values: vec![A::BaseField::ZERO; periodic_columns.len()],
+            evaluations: vec![A::BaseField::ZERO; air.context().num_main_transition_constraints()],
+            periodic_columns,
+        }
+    }
+
+    /// Evaluates the constraints over the rows at `step` and `step + 1`, and returns the index of
+    /// the first constraint which does not evaluate to zero, if any.
+    fn find_failed_constraint(
+        &mut self,
+        trace: &TraceTable<A::BaseField>,
+        step: usize,
+    ) -> Option<usize> {
+        trace.read_row_into(step, self.frame.current_mut());
+        trace.read_row_into((step + 1) % trace.length(), self.frame.next_mut());
+        for (value, column) in self.periodic_values.iter_mut().zip(&self.periodic_columns) {
+            *value = column[step % column.len()];
+        }
+
+        self.evaluations.fill(A::BaseField::ZERO);
+        self.air.evaluate_transition(&self.frame, &self.periodic_values, &mut self.evaluations);
+        self.evaluations.iter().position(|value| *value != A::BaseField::ZERO)
+    }
+}
+
+fn read_row<E: StarkField>(trace: &TraceTable<E>, step: usize) -> Vec<E> {
+    let mut row = vec![E::ZERO; trace.main_trace_width()];
+    trace.read_row_into(step, &mut row);
+    row
+}
+
+// CONSTRAINT ERROR
+// ================================================================================================
+
+/// Describes where an execution trace fails to satisfy the constraints of an AIR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConstraintError<E: StarkField> {
+    /// The dimensions of the trace, as (width, length), differ from the ones the AIR was
+    /// instantiated for.
+    TraceShapeMismatch { expected: (usize, usize), actual: (usize, usize) },
+    /// A transition constraint does not evaluate to zero over the rows at `step` and `step + 1`.
+    Transition { step: usize, constraint: usize, value: E, current: Vec<E>, next: Vec<E> },
+    /// The value in `column` at `step` differs from the one asserted by the AIR.
+    Assertion { step: usize, column: usize, expected: E, actual: E, row: Vec<E> },
+}
+
+impl<E: StarkField> ConstraintError<E> {
+    /// Returns the step at which the trace fails; zero if the trace has a wrong shape.
+    pub fn step(&self) -> usize {
+        match self {
+            Self::TraceShapeMismatch { .. } => 0,
+            Self::Transition { step, .. } | Self::Assertion { step, .. } => *step,
+        }
+    }
+}
+
+impl<E: StarkField> fmt::Display for ConstraintError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TraceShapeMismatch { expected, actual } => write!(
+                f,
+                "expected a trace of width {} and length {}, but got width {} and length {}",
+                expected.0, expected.1, actual.0, actual.1
+            ),
+            Self::Transition { step, constraint, value, current, next } => {
+                writeln!(
+                    f,
+                    "transition constraint {constraint} failed at step {step}: evaluates to \
+                     {value}:"
+                )?;
+                writeln!(f, "{}", format_trace_row(*step, current))?;
+                write!(f, "{}", format_trace_row(step + 1, next))
+            },
+            Self::Assertion { step, column, expected, actual, row } => {
+                writeln!(
+                    f,
+                    "assertion on column {column} failed at step {step}: expected {expected}, \
+                     but found {actual}:"
+                )?;
+                write!(f, "{}", format_trace_row(*step, row))
+            },
+        }
+    }
+}
+
+impl<E: StarkField> std::error::Error for ConstraintError<E> {}
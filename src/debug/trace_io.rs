@@ -0,0 +1,219 @@
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Trace, TraceInfo, TraceTable,
+};
+
+use crate::parse::parse_element;
+
+/// Name of the column holding step numbers in exported traces.
+const STEP_COLUMN: &str = "step";
+
+// NAMED TRACE
+// ================================================================================================
+
+/// An execution trace together with names of its columns, which can be exported to and imported
+/// from CSV and JSON.
+///
+/// Every exported row starts with its step number; field elements are written as decimal
+/// numbers, and can be read from decimal or 0x-prefixed hexadecimal numbers smaller than the
+/// field modulus.
+pub struct NamedTrace {
+    pub names: Vec<String>,
+    pub trace: TraceTable<BaseElement>,
+}
+
+impl NamedTrace {
+    /// Returns a trace with columns named `r0`, `r1`, etc.
+    pub fn new(trace: TraceTable<BaseElement>) -> Self {
+        let names = (0..trace.width()).map(|i| format!("r{i}")).collect();
+        Self { names, trace }
+    }
+
+    /// Returns a trace with the specified column names.
+    ///
+    /// # Panics
+    /// Panics if the number of names differs from the width of the trace, or if a name is empty
+    /// or contains a comma, a quote or a line break.
+    pub fn with_names(trace: TraceTable<BaseElement>, names: &[&str]) -> Self {
+        assert_eq!(trace.width(), names.len(), "expected a name for every trace column");
+        for name in names {
+            assert!(
+                !name.is_empty() && !name.contains([',', '"', '\n', '\r']),
+                "'{name}' is not a valid column name"
+            );
+        }
+        Self { names: names.iter().map(ToString::to_string).collect(), trace }
+    }
+
+    /// Returns the trace as CSV with a header row of column names.
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{STEP_COLUMN},{}\n", self.names.join(","));
+        for (step, row) in self.rows().enumerate() {
+            let values: Vec<String> = row.iter().map(ToString::to_string).collect();
+            csv.push_str(&format!("{step},{}\n", values.join(",")));
+        }
+        csv
+    }
+
+    /// Parses a trace from CSV in the format written by [Self::to_csv]; whitespace around values
+    /// and empty lines are ignored.
+    pub fn from_csv(csv: &str) -> Result<Self, TraceFormatError> {
+        let mut lines = csv.lines().map(str::trim).filter(|line| !line.is_empty());
+        let header = lines.next().ok_or(TraceFormatError::MissingHeader)?;
+        let mut names = header.split(',').map(str::trim);
+        if names.next() != Some(STEP_COLUMN) {
+            return Err(TraceFormatError::MissingHeader);
+        }
+        let names: Vec<String> = names.map(String::from).collect();
+
+        let rows = lines.map(|line| {
+            let mut values = line.split(',').map(str::trim);
+            let step = values.next().unwrap_or_default().to_string();
+            (step, values.map(String::from).collect())
+        });
+        Self::from_rows(names, rows)
+    }
+
+    /// Returns the trace as a JSON object of the form
+    /// `{"columns": [...], "rows": [{"step": 0, "values": [...]}, ...]}`.
+    pub fn to_json(&self) -> String {
+        let rows = self
+            .rows()
+            .enumerate()
+            .map(|(step, row)| JsonRow {
+                step,
+                values: row.iter().map(ToString::to_string).collect(),
+            })
+            .collect();
+        let trace = JsonTrace { columns: self.names.clone(), rows };
+        serde_json::to_string_pretty(&trace).expect("failed to serialize trace")
+    }
+
+    /// Parses a trace from JSON in the format written by [Self::to_json].
+    pub fn from_json(json: &str) -> Result<Self, TraceFormatError> {
+        let trace: JsonTrace = serde_json::from_str(json)
+            .map_err(|err| TraceFormatError::MalformedJson(err.to_string()))?;
+        let rows = trace.rows.into_iter().map(|row| (row.step.to_string(), row.values));
+        Self::from_rows(trace.columns, rows)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn rows(&self) -> impl Iterator<Item = Vec<BaseElement>> + '_ {
+        (0..self.trace.length()).map(|step| {
+            let mut row = vec![BaseElement::ZERO; self.trace.width()];
+            self.trace.read_row_into(step, &mut row);
+            row
+        })
+    }
+
+    /// Builds a trace from rows of (step, values) pairs; steps must start at zero and increase
+    /// by one.
+    fn from_rows(
+        names: Vec<String>,
+        rows: impl Iterator<Item = (String, Vec<String>)>,
+    ) -> Result<Self, TraceFormatError> {
+        let width = names.len();
+        if width == 0 || width > TraceInfo::MAX_TRACE_WIDTH {
+            return Err(TraceFormatError::InvalidWidth(width));
+        }
+
+        let mut columns = vec![Vec::new(); width];
+        for (expected_step, (step, values)) in rows.enumerate() {
+            if step != expected_step.to_string() {
+                return Err(TraceFormatError::UnexpectedStep { expected: expected_step, step });
+            }
+            if values.len() != width {
+                return Err(TraceFormatError::RowWidthMismatch {
+                    step: expected_step,
+                    expected: width,
+                    actual: values.len(),
+                });
+            }
+            for ((column, name), value) in columns.iter_mut().zip(&names).zip(&values) {
+                let value = parse_element(value).map_err(|message| {
+                    TraceFormatError::InvalidElement {
+                        step: expected_step,
+                        column: name.clone(),
+                        message,
+                    }
+                })?;
+                column.push(value);
+            }
+        }
+
+        let length = columns[0].len();
+        if length < TraceInfo::MIN_TRACE_LENGTH || !length.is_power_of_two() {
+            return Err(TraceFormatError::InvalidLength(length));
+        }
+        Ok(Self { names, trace: TraceTable::init(columns) })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonTrace {
+    columns: Vec<String>,
+    rows: Vec<JsonRow>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonRow {
+    step: usize,
+    values: Vec<String>,
+}
+
+// TRACE FORMAT ERROR
+// ================================================================================================
+
+/// Represents an error returned when an exported trace cannot be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceFormatError {
+    /// The CSV input does not start with a header row whose first column is `step`.
+    MissingHeader,
+    /// The JSON input is not a valid trace object.
+    MalformedJson(String),
+    /// The trace has no columns, or more columns than a trace can hold.
+    InvalidWidth(usize),
+    /// The number of rows is not a power of two, or is smaller than the minimum trace length.
+    InvalidLength(usize),
+    /// Rows are missing or out of order.
+    UnexpectedStep { expected: usize, step: String },
+    /// A row has a different number of values than there are columns.
+    RowWidthMismatch { step: usize, expected: usize, actual: usize },
+    /// A value is not a valid field element.
+    InvalidElement { step: usize, column: String, message: String },
+}
+
+impl fmt::Display for TraceFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "expected a header row starting with '{STEP_COLUMN}'"),
+            Self::MalformedJson(err) => write!(f, "malformed trace JSON: {err}"),
+            Self::InvalidWidth(width) => write!(
+                f,
+                "trace width must be between 1 and {}, but was {width}",
+                TraceInfo::MAX_TRACE_WIDTH
+            ),
+            Self::InvalidLength(length) => write!(
+                f,
+                "trace length must be a power of two of at least {}, but was {length}",
+                TraceInfo::MIN_TRACE_LENGTH
+            ),
+            Self::UnexpectedStep { expected, step } => {
+                write!(f, "expected row for step {expected}, but found step '{step}'")
+            },
+            Self::RowWidthMismatch { step, expected, actual } => {
+                write!(f, "row for step {step} has {actual} values, but expected {expected}")
+            },
+            Self::InvalidElement { step, column, message } => {
+                write!(f, "invalid value in column '{column}' at step {step}: {message}")
+            },
+        }
+    }
+}
+
+impl std::error::Error for TraceFormatError {}
@@ -0,0 +1,109 @@
+use core::fmt;
+use std::collections::HashSet;
+
+use winterfell::{math::FieldElement, Air, Trace, TraceTable};
+
+use super::{check_constraints, ConstraintError, TransitionEvaluator};
+
+// UNDER-CONSTRAINED CELL DETECTOR
+// ================================================================================================
+
+/// How much freedom a prover has in choosing the value of a trace cell.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CellFreedom {
+    /// The value can change while all assertions and transition constraints remain satisfied.
+    Unconstrained,
+    /// Changing the value breaks only the transition constraints from the row of the cell to the
+    /// next row. Neither assertions nor earlier rows determine the value, so a prover can choose
+    /// it and compute the following rows from it.
+    FreeInput,
+}
+
+/// A trace cell whose value is not fully determined by the constraints of an AIR.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnderConstrainedCell {
+    pub step: usize,
+    pub column: usize,
+    pub freedom: CellFreedom,
+}
+
+/// Perturbs every cell of a valid trace in turn, and returns the cells whose values are not
+/// determined by the assertions and transition constraints of the AIR, ordered by step and
+/// column.
+///
+/// Every cell is perturbed by a small and a large offset; a cell is reported if either of the
+/// perturbed values keeps the constraints which apply to the cell satisfied. Cells which are
+/// meant to be private inputs of the computation are reported as [CellFreedom::FreeInput].
+///
+/// # Errors
+/// Returns an error if the trace does not satisfy the constraints of the AIR to begin with.
+pub fn find_under_constrained_cells<A: Air>(
+    air: &A,
+    trace: &TraceTable<A::BaseField>,
+) -> Result<Vec<UnderConstrainedCell>, ConstraintError<A::BaseField>> {
+    check_constraints(air, trace)?;
+
+    // a perturbed value always breaks an assertion on its cell, since the trace is valid
+    let mut asserted_cells = HashSet::new();
+    for assertion in air.get_assertions() {
+        let column = assertion.column();
+        assertion.apply(trace.length(), |step, _| {
+            asserted_cells.insert((step, column));
+        });
+    }
+
+    let offsets = [A::BaseField::ONE, A::BaseField::from(0x9e37_79b9_u32)];
+    let mut evaluator = TransitionEvaluator::new(air);
+    let mut trace = trace.clone();
+    let mut cells = Vec::new();
+    for step in 0..trace.length() {
+        for column in 0..trace.main_trace_width() {
+            if asserted_cells.contains(&(step, column)) {
+                continue;
+            }
+
+            let value = trace.get(column, step);
+            let mut freedom = None;
+            for offset in offsets {
+                trace.set(column, step, value + offset);
+                let breaks_incoming = step
+                    .checked_sub(1)
+                    .filter(|&previous| previous < evaluator.num_steps)
+                    .is_some_and(|previous| {
+                        evaluator.find_failed_constraint(&trace, previous).is_some()
+                    });
+                let breaks_outgoing = step < evaluator.num_steps
+                    && evaluator.find_failed_constraint(&trace, step).is_some();
+                match (breaks_incoming, breaks_outgoing) {
+                    (false, false) => freedom = Some(CellFreedom::Unconstrained),
+                    (false, true) => {
+                        freedom = freedom.or(Some(CellFreedom::FreeInput));
+                    },
+                    _ => (),
+                }
+            }
+            trace.set(column, step, value);
+
+            if let Some(freedom) = freedom {
+                cells.push(UnderConstrainedCell { step, column, freedom });
+            }
+        }
+    }
+    Ok(cells)
+}
+
+impl fmt::Display for UnderConstrainedCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { step, column, freedom } = self;
+        match freedom {
+            CellFreedom::Unconstrained => {
+                write!(f, "column {column} at step {step} is not constrained")
+            },
+            CellFreedom::FreeInput => write!(
+                f,
+                "column {column} at step {step} is a free input: it is not determined by \
+                 assertions or earlier rows"
+            ),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use core::fmt;
+use std::io;
+
+use winter_utils::DeserializationError;
+use winterfell::{ProverError, VerifierError};
+
+use crate::bundle::BundleError;
+use crate::security::SecurityError;
+
+// CRATE ERROR
+// ================================================================================================
+
+/// Represents an error returned while generating, storing, loading or verifying a proof.
+#[derive(Debug)]
+pub enum Error {
+    /// This error occurs when reading from or writing to a file fails.
+    Io(io::Error),
+    /// This error occurs when a proof or its public inputs cannot be deserialized.
+    Deserialization(DeserializationError),
+    /// This error occurs when the prover fails to generate a proof.
+    Prover(ProverError),
+    /// This error occurs when a proof fails to verify.
+    Verifier(VerifierError),
+    /// This error occurs when the public inputs are inconsistent with each other or with the
+    /// proof they are supplied with.
+    PublicInputMismatch(String),
+    /// This error occurs when a proof bundle is malformed.
+    Bundle(BundleError),
+    /// This error occurs when a proof does not satisfy the security requirements of the
+    /// verifier.
+    Security(SecurityError),
+}
+
+impl Error {
+    /// Returns the process exit code which binaries use to report this error.
+    ///
+    /// Exit code 2 is left for command-line usage errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Io(_) => 3,
+            Self::Deserialization(_) => 4,
+            Self::Prover(_) => 5,
+            Self::Verifier(_) => 6,
+            Self::PublicInputMismatch(_) => 7,
+            Self::Bundle(_) => 8,
+            Self::Security(_) => 9,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::Deserialization(err) => write!(f, "deserialization failed: {err}"),
+            Self::Prover(err) => write!(f, "proof generation failed: {err}"),
+            Self::Verifier(err) => write!(f, "proof verification failed: {err}"),
+            Self::PublicInputMismatch(msg) => write!(f, "public inputs mismatch: {msg}"),
+            Self::Bundle(err) => write!(f, "invalid proof bundle: {err}"),
+            Self::Security(err) => write!(f, "insufficient proof security: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Bundle(err) => Some(err),
+            Self::Security(err) => Some(err),
+            // winterfell errors do not implement std::error::Error; their messages are part of
+            // the message of this error instead
+            Self::Deserialization(_) | Self::Prover(_) | Self::Verifier(_) => None,
+            Self::PublicInputMismatch(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<DeserializationError> for Error {
+    fn from(err: DeserializationError) -> Self {
+        Self::Deserialization(err)
+    }
+}
+
+impl From<ProverError> for Error {
+    fn from(err: ProverError) -> Self {
+        Self::Prover(err)
+    }
+}
+
+impl From<VerifierError> for Error {
+    fn from(err: VerifierError) -> Self {
+        Self::Verifier(err)
+    }
+}
+
+impl From<BundleError> for Error {
+    fn from(err: BundleError) -> Self {
+        Self::Bundle(err)
+    }
+}
+
+impl From<SecurityError> for Error {
+    fn from(err: SecurityError) -> Self {
+        Self::Security(err)
+    }
+}
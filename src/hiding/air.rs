@@ -0,0 +1,128 @@
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+use super::{HiddenSeedPublicInputs, COMMITMENT_STEP};
+use crate::air::enforce_hash_copy;
+use crate::utils::not;
+use crate::{result_step, AlgebraicPermutation, Rescue4x14, TRACE_WIDTH};
+
+// HIDDEN SEED AIR
+// ================================================================================================
+
+/// AIR for a chain of hashes `result = H^iterations(seed)` where the seed is not public.
+///
+/// Transition constraints are enforced only up to the result; the remaining rows of the trace
+/// hold random values.
+pub struct HiddenSeedAir {
+    context: AirContext<BaseElement>,
+    result: [BaseElement; 2],
+    result_step: usize,
+    seed_commitment: Option<[BaseElement; 2]>,
+}
+
+impl Air for HiddenSeedAir {
+    type BaseField = BaseElement;
+    type PublicInputs = HiddenSeedPublicInputs;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(
+        trace_info: TraceInfo,
+        pub_inputs: HiddenSeedPublicInputs,
+        options: ProofOptions,
+    ) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        assert!(pub_inputs.iterations > 0, "number of iterations must be greater than zero");
+        let result_step = result_step::<Rescue4x14>(pub_inputs.iterations);
+        assert!(
+            result_step < trace_info.length(),
+            "trace of length {} cannot hold {} iterations",
+            trace_info.length(),
+            pub_inputs.iterations
+        );
+
+        let cycles = vec![Rescue4x14::CYCLE_LENGTH, trace_info.length()];
+        let degrees = vec![
+            TransitionConstraintDegree::with_cycles(Rescue4x14::CONSTRAINT_DEGREE, cycles);
+            TRACE_WIDTH
+        ];
+
+        // the commitment is located at the end of the first cycle; for a single iteration it is
+        // the result itself, and the verifier makes sure the two are equal
+        let seed_commitment =
+            pub_inputs.seed_commitment.filter(|_| result_step != COMMITMENT_STEP);
+
+        // the seed must be padded with zeros, the result must be at the end of the last cycle,
+        // and the commitment at the end of the first one
+        let num_assertions = if seed_commitment.is_some() { 6 } else { 4 };
+
+        HiddenSeedAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            result: pub_inputs.result,
+            result_step,
+            seed_commitment,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        // expected state width is equal to the width of the permutation state
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // split periodic values into hash_flag, the mask of constrained steps and Rescue round
+        // constants
+        let hash_flag = periodic_values[0];
+        let enabled = periodic_values[1];
+        let ark = &periodic_values[2..];
+
+        // when hash_flag = 1, constraints for Rescue round are enforced
+        Rescue4x14::enforce_round(result, current, next, ark, enabled * hash_flag);
+
+        // when hash_flag = 0, constraints for copying hash values to the next
+        // step are enforced.
+        let copy_flag = not(hash_flag);
+        enforce_hash_copy(result, current, next, enabled * copy_flag);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the seed is not known, but it must be padded with zeros in the same way as every
+        // other input of the hash chain
+        let mut assertions = vec![
+            Assertion::single(2, 0, BaseElement::ZERO),
+            Assertion::single(3, 0, BaseElement::ZERO),
+            Assertion::single(0, self.result_step, self.result[0]),
+            Assertion::single(1, self.result_step, self.result[1]),
+        ];
+        if let Some(commitment) = self.seed_commitment {
+            assertions.push(Assertion::single(0, COMMITMENT_STEP, commitment[0]));
+            assertions.push(Assertion::single(1, COMMITMENT_STEP, commitment[1]));
+        }
+        assertions
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        // transition constraints are enforced for all steps before the result; the transition
+        // from the result into the first random row is not constrained
+        let mut enabled = vec![BaseElement::ZERO; self.trace_length()];
+        enabled[..self.result_step].fill(BaseElement::ONE);
+
+        let mut result = vec![Rescue4x14::get_round_mask(), enabled];
+        result.append(&mut Rescue4x14::get_round_constants());
+        result
+    }
+}
@@ -0,0 +1,243 @@
+//! Proves knowledge of a hidden preimage of a hash chain result.
+//!
+//! Unlike [TrainAir](crate::TrainAir), the seed is not a public input: only the result, the
+//! number of iterations and, optionally, a commitment to the seed are revealed. The commitment
+//! is the first link of the chain, i.e. `H(seed)`; it binds the proof to the seed but hides it
+//! only as well as the seed itself is unpredictable.
+//!
+//! Values opened by the verifier are evaluations of the trace polynomials outside of the trace
+//! domain, and each of them depends on every row of the trace. To keep these values from
+//! leaking the seed, the trace is extended by at least [blinding_steps] rows filled with random
+//! values, and transition constraints are switched off for these rows. With as many random rows
+//! as there are trace evaluations which the openings depend on, the opened values are uniformly
+//! distributed; this is a heuristic argument, and the proofs are not claimed to be formally
+//! zero-knowledge.
+
+use rand::Rng;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement, StarkField, ToElements},
+    Proof, ProofOptions, Prover, TraceTable,
+};
+
+use crate::security::verify_with_policy;
+use crate::{
+    check_blowup_factor, check_trace_length, hash, result_step, AirId, AlgebraicPermutation,
+    Blake3, BundlePublicInputs, Error, Rescue4x14, SecurityLevel, SecurityPolicy, TRACE_WIDTH,
+};
+
+mod air;
+pub use air::HiddenSeedAir;
+
+mod prover;
+pub use prover::HiddenSeedProver;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Trace step at which the commitment to the seed is located: the end of the first cycle.
+pub const COMMITMENT_STEP: usize = Rescue4x14::CYCLE_LENGTH - 1;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HiddenSeedPublicInputs {
+    /// Result of the hash chain, i.e. `H^iterations(seed)`.
+    pub result: [BaseElement; 2],
+    /// Number of chained Rescue hashes.
+    pub iterations: usize,
+    /// Optional commitment to the seed, i.e. `H(seed)`.
+    pub seed_commitment: Option<[BaseElement; 2]>,
+}
+
+impl ToElements<BaseElement> for HiddenSeedPublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.result.to_vec();
+        result.push(BaseElement::from(self.iterations as u64));
+        if let Some(commitment) = self.seed_commitment {
+            result.extend_from_slice(&commitment);
+        }
+        result
+    }
+}
+
+impl Serializable for HiddenSeedPublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_many(self.result);
+        target.write_u64(self.iterations as u64);
+        match self.seed_commitment {
+            Some(commitment) => {
+                target.write_bool(true);
+                target.write_many(commitment);
+            },
+            None => target.write_bool(false),
+        }
+    }
+}
+
+impl Deserializable for HiddenSeedPublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let result = source.read()?;
+        let iterations = source.read_u64()?;
+        let iterations = usize::try_from(iterations).map_err(|_| {
+            DeserializationError::InvalidValue(format!(
+                "{iterations} iterations do not fit into usize"
+            ))
+        })?;
+        let seed_commitment = if source.read_bool()? { Some(source.read()?) } else { None };
+        Ok(HiddenSeedPublicInputs { result, iterations, seed_commitment })
+    }
+}
+
+impl BundlePublicInputs for HiddenSeedPublicInputs {
+    const AIR_ID: AirId = AirId::HiddenSeed;
+}
+
+// TRACE BUILDER
+// ================================================================================================
+
+/// Returns the minimum number of random rows needed to hide the trace from a verifier which
+/// sees all openings of a proof generated with `options`.
+///
+/// Every opened value depends on the trace polynomials at some point `x` and at `x * g`, where
+/// `g` generates the trace domain:
+/// - each query opens the trace and the constraint composition polynomial at one point of the
+///   evaluation domain, and the first FRI layer at `folding_factor` points including that one;
+///   FRI layer values are evaluations of the DEEP composition polynomial, which combines the
+///   trace and constraint composition polynomials;
+/// - the out-of-domain frame opens the trace at `z` and `z * g`, and the constraint composition
+///   polynomial at `z`, which depends on the same two points.
+pub fn blinding_steps(options: &ProofOptions) -> usize {
+    let points_per_query = options.to_fri_options().folding_factor();
+    2 * options.num_queries() * points_per_query + 2
+}
+
+/// Returns the length of the execution trace needed to compute `iterations` chained hashes
+/// followed by at least `blinding_steps` random rows, or None if the length does not fit into
+/// usize.
+pub fn trace_length(iterations: usize, blinding_steps: usize) -> Option<usize> {
+    let cycle_length = Rescue4x14::CYCLE_LENGTH;
+    let num_cycles = iterations.checked_add(blinding_steps.div_ceil(cycle_length))?;
+    num_cycles.checked_next_power_of_two()?.checked_mul(cycle_length)
+}
+
+/// Returns the commitment to `seed` revealed by the proof, i.e. the first link of the chain.
+pub fn commit_to_seed(seed: [BaseElement; 2]) -> [BaseElement; 2] {
+    hash(seed)
+}
+
+/// Builds an execution trace computing `iterations` chained hashes starting from `seed`; all
+/// rows after the result are filled with values drawn from `rng`.
+///
+/// # Panics
+/// Panics if `iterations` is zero, or if the length of the trace does not fit into usize.
+pub fn build_trace<R: Rng>(
+    seed: [BaseElement; 2],
+    iterations: usize,
+    blinding_steps: usize,
+    rng: &mut R,
+) -> TraceTable<BaseElement> {
+    assert!(iterations > 0, "number of iterations must be greater than zero");
+
+    let trace_length = trace_length(iterations, blinding_steps).expect("too many iterations");
+    let result_step = result_step::<Rescue4x14>(iterations);
+    let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+
+    trace.fill(
+        |state| {
+            // initialize first state of the computation
+            state[0] = seed[0];
+            state[1] = seed[1];
+            state[2..].fill(BaseElement::ZERO);
+        },
+        |step, state| {
+            // compute the hash chain up to the result, and random values afterwards
+            if step >= result_step {
+                state.fill_with(|| BaseElement::new(rng.gen_range(0..BaseElement::MODULUS)));
+            } else if (step % Rescue4x14::CYCLE_LENGTH) < Rescue4x14::NUM_ROUNDS {
+                Rescue4x14::apply_round(state, step);
+            } else {
+                state[2..].fill(BaseElement::ZERO);
+            }
+        },
+    );
+    trace
+}
+
+// PROVE AND VERIFY
+// ================================================================================================
+
+/// Computes `iterations` chained Rescue hashes starting from `seed` and generates a STARK proof
+/// attesting that the prover knows a seed hashing to the result; the seed itself is not
+/// revealed. When `commit_seed` is true, a commitment to the seed is made public as well.
+pub fn prove_hidden_seed(
+    seed: [BaseElement; 2],
+    iterations: usize,
+    commit_seed: bool,
+    options: ProofOptions,
+) -> Result<(HiddenSeedPublicInputs, Proof), Error> {
+    if iterations == 0 {
+        return Err(Error::PublicInputMismatch(
+            "number of iterations must be greater than zero".to_string(),
+        ));
+    }
+    let blinding_steps = blinding_steps(&options);
+    check_trace_length(trace_length(iterations, blinding_steps), &options)?;
+    check_blowup_factor(&options, Rescue4x14::CONSTRAINT_DEGREE + 1)?;
+
+    let trace = build_trace(seed, iterations, blinding_steps, &mut rand::thread_rng());
+    let prover = HiddenSeedProver::new(options, iterations, commit_seed);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace)?;
+    Ok((pub_inputs, proof))
+}
+
+/// Verifies that `proof` attests to the knowledge of a seed for the hash chain described by
+/// `pub_inputs`, and returns the security level of the proof.
+pub fn verify_hidden_seed(
+    pub_inputs: HiddenSeedPublicInputs,
+    proof: Proof,
+) -> Result<SecurityLevel, Error> {
+    verify_hidden_seed_with(pub_inputs, proof, &SecurityPolicy::default())
+}
+
+/// Same as [verify_hidden_seed], but accepts the proof under the specified security policy.
+pub fn verify_hidden_seed_with(
+    pub_inputs: HiddenSeedPublicInputs,
+    proof: Proof,
+    policy: &SecurityPolicy,
+) -> Result<SecurityLevel, Error> {
+    let trace_info = proof.trace_info();
+    if trace_info.width() != TRACE_WIDTH {
+        return Err(Error::PublicInputMismatch(format!(
+            "expected trace width {TRACE_WIDTH}, but proof trace width is {}",
+            trace_info.width()
+        )));
+    }
+    if pub_inputs.iterations == 0 {
+        return Err(Error::PublicInputMismatch(
+            "number of iterations must be greater than zero".to_string(),
+        ));
+    }
+    if result_step::<Rescue4x14>(pub_inputs.iterations) >= trace_info.length() {
+        return Err(Error::PublicInputMismatch(format!(
+            "trace of length {} cannot hold {} iterations",
+            trace_info.length(),
+            pub_inputs.iterations
+        )));
+    }
+    if let Some(commitment) = pub_inputs.seed_commitment {
+        // for a single iteration the commitment is the result and is not asserted separately
+        if pub_inputs.iterations == 1 && commitment != pub_inputs.result {
+            return Err(Error::PublicInputMismatch(
+                "for a single iteration the seed commitment must be equal to the result"
+                    .to_string(),
+            ));
+        }
+    }
+    // the constraints also depend on a periodic column switching them off after the result
+    check_blowup_factor(proof.options(), Rescue4x14::CONSTRAINT_DEGREE + 1)?;
+
+    verify_with_policy::<HiddenSeedAir, Blake3>(proof, pub_inputs, policy)
+}
@@ -0,0 +1,71 @@
+use winter_air::PartitionOptions;
+use winterfell::{
+    crypto::{DefaultRandomCoin, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    matrix::ColMatrix,
+    AuxRandElements, DefaultConstraintEvaluator, DefaultTraceLde, ProofOptions, Prover,
+    StarkDomain, TraceInfo, TracePolyTable, TraceTable,
+};
+
+use super::{HiddenSeedAir, HiddenSeedPublicInputs, COMMITMENT_STEP};
+use crate::{result_step, Blake3, Rescue4x14};
+
+pub struct HiddenSeedProver {
+    options: ProofOptions,
+    iterations: usize,
+    commit_seed: bool,
+}
+
+impl HiddenSeedProver {
+    /// Returns a prover for a hash chain of `iterations` chained hashes; when `commit_seed` is
+    /// true, the public inputs include a commitment to the seed.
+    pub fn new(options: ProofOptions, iterations: usize, commit_seed: bool) -> Self {
+        Self { options, iterations, commit_seed }
+    }
+}
+
+impl Prover for HiddenSeedProver {
+    type BaseField = BaseElement;
+    type Air = HiddenSeedAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+    type VC = MerkleTree<Self::HashFn>;
+    type RandomCoin = DefaultRandomCoin<Blake3>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Blake3, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, HiddenSeedAir, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> HiddenSeedPublicInputs {
+        let result_step = result_step::<Rescue4x14>(self.iterations);
+        HiddenSeedPublicInputs {
+            result: [trace.get(0, result_step), trace.get(1, result_step)],
+            iterations: self.iterations,
+            seed_commitment: self
+                .commit_seed
+                .then(|| [trace.get(0, COMMITMENT_STEP), trace.get(1, COMMITMENT_STEP)]),
+        }
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_option: PartitionOptions,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain, partition_option)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        air: &'a HiddenSeedAir,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: winterfell::ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
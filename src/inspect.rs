@@ -0,0 +1,90 @@
+use winter_utils::Serializable;
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Proof,
+};
+
+use crate::{
+    batch, hiding, merkle, sponge, AirId, BundleHeader, Error, ProofBundle, PublicInputs,
+};
+
+// PROOF SIZE
+// ================================================================================================
+
+/// Number of bytes taken by each part of a serialized proof.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ProofSize {
+    /// Trace info, proof options and the number of unique queries.
+    pub context: usize,
+    /// Commitments to the trace, the constraint evaluations and the FRI layers.
+    pub commitments: usize,
+    /// Queried trace values together with their Merkle authentication paths.
+    pub trace_openings: usize,
+    /// Queried constraint evaluations together with their Merkle authentication paths.
+    pub constraint_openings: usize,
+    /// Trace states and constraint evaluations at the out-of-domain point.
+    pub ood_frame: usize,
+    /// Queried values and authentication paths of all FRI layers.
+    pub fri_layers: usize,
+    /// Coefficients of the FRI remainder polynomial.
+    pub fri_remainder: usize,
+    /// Proof-of-work nonce found by grinding.
+    pub pow_nonce: usize,
+    /// Proof for the GKR-based auxiliary trace, if any; one byte when absent.
+    pub gkr_proof: usize,
+}
+
+impl ProofSize {
+    /// Returns the sizes of the parts of the proof; they add up to the size of the serialized
+    /// proof.
+    pub fn of(proof: &Proof) -> Self {
+        // the remainder is stored as a byte vector prefixed with its length as a u16
+        let fri_remainder = proof.fri_proof.num_remainder_elements::<BaseElement>()
+            * BaseElement::ELEMENT_BYTES
+            + 2;
+        let trace_openings = proof.trace_queries.iter().map(|queries| queries.to_bytes().len());
+
+        Self {
+            context: proof.context.to_bytes().len() + 1,
+            commitments: proof.commitments.to_bytes().len(),
+            trace_openings: trace_openings.sum(),
+            constraint_openings: proof.constraint_queries.to_bytes().len(),
+            ood_frame: proof.ood_frame.to_bytes().len(),
+            fri_layers: proof.fri_proof.to_bytes().len() - fri_remainder,
+            fri_remainder,
+            pow_nonce: proof.pow_nonce.to_bytes().len(),
+            gkr_proof: proof.gkr_proof.to_bytes().len(),
+        }
+    }
+
+    /// Returns the size of the serialized proof.
+    pub fn total(&self) -> usize {
+        self.context
+            + self.commitments
+            + self.trace_openings
+            + self.constraint_openings
+            + self.ood_frame
+            + self.fri_layers
+            + self.fri_remainder
+            + self.pow_nonce
+            + self.gkr_proof
+    }
+}
+
+// BUNDLE PARSING
+// ================================================================================================
+
+/// Parses a proof bundle for any AIR and returns its header and proof.
+pub fn read_bundle_proof(bytes: &[u8]) -> Result<(BundleHeader, Proof), Error> {
+    let header = BundleHeader::from_bytes(bytes)?;
+    let proof = match header.air {
+        AirId::DoWork => ProofBundle::<PublicInputs>::from_bytes(bytes)?.proof,
+        AirId::Sponge => ProofBundle::<sponge::SpongePublicInputs>::from_bytes(bytes)?.proof,
+        AirId::Merkle => ProofBundle::<merkle::MerklePublicInputs>::from_bytes(bytes)?.proof,
+        AirId::HiddenSeed => {
+            ProofBundle::<hiding::HiddenSeedPublicInputs>::from_bytes(bytes)?.proof
+        },
+        AirId::Batch => ProofBundle::<batch::BatchPublicInputs>::from_bytes(bytes)?.proof,
+    };
+    Ok((header, proof))
+}
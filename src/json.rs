@@ -0,0 +1,196 @@
+//! JSON representation of public inputs and proof metadata.
+//!
+//! Field elements are written as canonical decimal strings, and can be read from decimal or
+//! 0x-prefixed hexadecimal strings; values which are not smaller than the f128 modulus are
+//! rejected.
+
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use winterfell::{math::fields::f128::BaseElement, FieldExtension, Proof, ProofOptions};
+
+use crate::parse::{parse_element, parse_field_extension, parse_hash_function};
+use crate::{validate_proof_options, AirId, BundlePublicInputs, HashFunction, ProofBundle};
+
+// PROOF METADATA
+// ================================================================================================
+
+/// Describes a proof without its contents: the AIR and the parameters it was generated with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofMetadata {
+    pub air: AirId,
+    pub hash_fn: HashFunction,
+    pub trace_width: usize,
+    pub trace_length: usize,
+    #[serde(with = "options")]
+    pub options: ProofOptions,
+}
+
+impl ProofMetadata {
+    /// Returns metadata for the proof stored in the bundle.
+    pub fn from_bundle<P: BundlePublicInputs>(bundle: &ProofBundle<P>) -> Self {
+        Self::from_proof(P::AIR_ID, bundle.hash_fn, &bundle.proof)
+    }
+
+    /// Returns metadata for a proof of the specified AIR committed to using `hash_fn`.
+    pub fn from_proof(air: AirId, hash_fn: HashFunction, proof: &Proof) -> Self {
+        let trace_info = proof.trace_info();
+        Self {
+            air,
+            hash_fn,
+            trace_width: trace_info.width(),
+            trace_length: trace_info.length(),
+            options: proof.options().clone(),
+        }
+    }
+}
+
+// FIELD ELEMENTS
+// ================================================================================================
+
+/// Serializes a single field element as a decimal string; use with `#[serde(with = "...")]`.
+pub mod element {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &BaseElement, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BaseElement, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_element(&value).map_err(D::Error::custom)
+    }
+}
+
+/// Serializes an array of field elements as an array of decimal strings; use with
+/// `#[serde(with = "...")]`.
+pub mod elements {
+    use super::*;
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        values: &[BaseElement; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(ToString::to_string))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[BaseElement; N], D::Error> {
+        let values = Vec::<String>::deserialize(deserializer)?;
+        let len = values.len();
+        let elements = values
+            .iter()
+            .map(|value| parse_element(value))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(D::Error::custom)?;
+        elements
+            .try_into()
+            .map_err(|_| D::Error::custom(format!("expected {N} field elements, but got {len}")))
+    }
+}
+
+// PROOF OPTIONS
+// ================================================================================================
+
+/// Serializes proof options as an object with named parameters; deserialized options are
+/// validated.
+mod options {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct RawOptions {
+        num_queries: usize,
+        blowup_factor: usize,
+        grinding_factor: u32,
+        #[serde(with = "super::field_extension")]
+        field_extension: FieldExtension,
+        folding_factor: usize,
+        remainder_max_degree: usize,
+    }
+
+    pub fn serialize<S: Serializer>(
+        value: &ProofOptions,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let fri_options = value.to_fri_options();
+        RawOptions {
+            num_queries: value.num_queries(),
+            blowup_factor: value.blowup_factor(),
+            grinding_factor: value.grinding_factor(),
+            field_extension: value.field_extension(),
+            folding_factor: fri_options.folding_factor(),
+            remainder_max_degree: fri_options.remainder_max_degree(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ProofOptions, D::Error> {
+        let raw = RawOptions::deserialize(deserializer)?;
+        validate_proof_options(
+            raw.num_queries,
+            raw.blowup_factor,
+            raw.grinding_factor,
+            raw.field_extension,
+            raw.folding_factor,
+            raw.remainder_max_degree,
+        )
+        .map_err(D::Error::custom)
+    }
+}
+
+mod field_extension {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        value: &FieldExtension,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match value {
+            FieldExtension::None => "none",
+            FieldExtension::Quadratic => "quadratic",
+            FieldExtension::Cubic => "cubic",
+        })
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<FieldExtension, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_field_extension(&value).map_err(D::Error::custom)
+    }
+}
+
+// IDENTIFIERS
+// ================================================================================================
+
+impl Serialize for AirId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for AirId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        (1..=u8::MAX)
+            .filter_map(|id| AirId::try_from(id).ok())
+            .find(|air| air.to_string() == value)
+            .ok_or_else(|| D::Error::custom(format!("'{value}' is not a known AIR")))
+    }
+}
+
+impl Serialize for HashFunction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for HashFunction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_hash_function(&value).map_err(D::Error::custom)
+    }
+}
@@ -1,67 +1,350 @@
+use serde::{Deserialize, Serialize};
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 use winterfell::{
-    crypto::hashers::Blake3_256,
-    math::{fields::f128::BaseElement, FieldElement, ToElements},
-    TraceTable
+    crypto::hashers::{Blake3_192, Blake3_256, Sha3_256},
+    math::{fields::f128::BaseElement, FieldElement, StarkField, ToElements},
+    Proof, ProofOptions, Prover, TraceTable,
 };
 
 mod air;
 pub use air::TrainAir;
 
+pub mod batch;
+
+mod bundle;
+pub use bundle::{
+    AirId, BundleError, BundleHeader, BundlePublicInputs, CommitmentHasher, HashFunction,
+    ProofBundle, BUNDLE_HEADER_SIZE, BUNDLE_MAGIC, BUNDLE_VERSION,
+};
+
+pub mod cli;
+
+pub mod debug;
+
+mod errors;
+pub use errors::Error;
+
+pub mod hiding;
+
+mod inspect;
+pub use inspect::{read_bundle_proof, ProofSize};
+
+pub mod json;
+
+pub mod merkle;
+
+mod options;
+pub use options::{
+    is_extension_supported, options_for_security, production_options, validate_proof_options,
+    PRODUCTION_SECURITY_LEVEL,
+};
+
+mod parse;
+
 mod prover;
 pub use prover::DoWorkProver;
 
 mod rescue;
-use rescue::{CYCLE_LENGTH, NUM_ROUNDS};
+pub use rescue::{hash, hash_chain, Rescue4x14};
+
+mod security;
+use security::verify_with_policy;
+pub use security::{SecurityError, SecurityLevel, SecurityPolicy, DEFAULT_MIN_SECURITY_LEVEL};
+
+pub mod sponge;
 
 pub mod utils;
+pub use utils::permutation::AlgebraicPermutation;
+pub use utils::rescue::Rescue6x7;
 
 pub type Blake3 = Blake3_256<BaseElement>;
 
 pub const TRACE_WIDTH: usize = 4;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PublicInputs {
+    #[serde(with = "json::elements")]
     pub seed: [BaseElement; 2],
+    #[serde(with = "json::elements")]
     pub result: [BaseElement; 2],
+    /// Number of chained Rescue hashes, i.e. `result = H^iterations(seed)`.
+    pub iterations: usize,
 }
 
 impl ToElements<BaseElement> for PublicInputs {
     fn to_elements(&self) -> Vec<BaseElement> {
         let mut result = self.seed.to_vec();
         result.extend_from_slice(&self.result);
+        result.push(BaseElement::from(self.iterations as u64));
         result
     }
 }
 
-pub fn build_trace(seed: [BaseElement; 2]) -> TraceTable<BaseElement> {
+impl Serializable for PublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_many(self.seed);
+        target.write_many(self.result);
+        target.write_u64(self.iterations as u64);
+    }
+}
+
+impl Deserializable for PublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let seed = source.read()?;
+        let result = source.read()?;
+        let iterations = source.read_u64()?;
+        let iterations = usize::try_from(iterations).map_err(|_| {
+            DeserializationError::InvalidValue(format!(
+                "{iterations} iterations do not fit into usize"
+            ))
+        })?;
+        Ok(PublicInputs { seed, result, iterations })
+    }
+}
+
+impl BundlePublicInputs for PublicInputs {
+    const AIR_ID: AirId = AirId::DoWork;
+}
+
+/// Returns the length of the execution trace needed to compute `iterations` chained hashes, or
+/// None if the length does not fit into usize.
+///
+/// Every hash occupies one cycle of `P::CYCLE_LENGTH` steps; the number of cycles is padded to
+/// the next power of two because trace length must be a power of two.
+pub fn trace_length<P: AlgebraicPermutation>(iterations: usize) -> Option<usize> {
+    iterations.checked_next_power_of_two()?.checked_mul(P::CYCLE_LENGTH)
+}
+
+/// Returns the trace step at which the result of `iterations` chained hashes is located.
+pub fn result_step<P: AlgebraicPermutation>(iterations: usize) -> usize {
+    iterations.saturating_mul(P::CYCLE_LENGTH) - 1
+}
+
+/// Builds an execution trace computing `iterations` chained hashes starting from `seed`, where
+/// every hash is a single application of the permutation `P`.
+///
+/// The hash chain result is located at step [result_step]; when `iterations` is not a power of
+/// two, the remaining cycles keep hashing and are ignored by the AIR.
+///
+/// # Panics
+/// Panics if `iterations` is zero, or if the length of the trace does not fit into usize.
+pub fn build_trace<P: AlgebraicPermutation>(
+    seed: [BaseElement; 2],
+    iterations: usize,
+) -> TraceTable<BaseElement> {
+    assert!(iterations > 0, "number of iterations must be greater than zero");
+
     // Instantiate the trace with a given width and length; this will allocate all
     // required memory for the trace
-    let trace_length = CYCLE_LENGTH;
-        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
-
-        trace.fill(
-            |state| {
-                // initialize first state of the computation
-                state[0] = seed[0];
-                state[1] = seed[1];
-                state[2] = BaseElement::ZERO;
-                state[3] = BaseElement::ZERO;
-            },
-            |step, state| {
-                // execute the transition function for all steps
-                //
-                // for the first 14 steps in every cycle, compute a single round of
-                // Rescue hash; for the remaining 2 rounds, just carry over the values
-                // in the first two registers to the next step
-                if (step % CYCLE_LENGTH) < NUM_ROUNDS {
-                    rescue::apply_round(state, step);
-                } else {
-                    state[2] = BaseElement::ZERO;
-                    state[3] = BaseElement::ZERO;
-                }
-            },
-        );
+    let trace_length = trace_length::<P>(iterations).expect("too many iterations");
+    let mut trace = TraceTable::new(P::STATE_WIDTH, trace_length);
+
+    trace.fill(
+        |state| {
+            // initialize first state of the computation
+            state[0] = seed[0];
+            state[1] = seed[1];
+            state[2..].fill(BaseElement::ZERO);
+        },
+        |step, state| {
+            // execute the transition function for all steps
+            //
+            // for the first NUM_ROUNDS steps in every cycle, compute a single round of
+            // the permutation; for the remaining steps, just carry over the values
+            // in the first two registers to the next step
+            if (step % P::CYCLE_LENGTH) < P::NUM_ROUNDS {
+                P::apply_round(state, step);
+            } else {
+                state[2..].fill(BaseElement::ZERO);
+            }
+        },
+    );
     trace
 }
 
+/// Computes `iterations` chained Rescue hashes starting from `seed` and generates a STARK proof
+/// attesting to the correctness of the computation.
+pub fn prove_do_work(
+    seed: [BaseElement; 2],
+    iterations: usize,
+    options: ProofOptions,
+) -> Result<(PublicInputs, Proof), Error> {
+    prove_do_work_with::<Blake3>(seed, iterations, options)
+}
+
+/// Same as [prove_do_work], but commits to the trace and constraint evaluations using the hash
+/// function `H`.
+pub fn prove_do_work_with<H: CommitmentHasher>(
+    seed: [BaseElement; 2],
+    iterations: usize,
+    options: ProofOptions,
+) -> Result<(PublicInputs, Proof), Error> {
+    if iterations == 0 {
+        return Err(Error::PublicInputMismatch(
+            "number of iterations must be greater than zero".to_string(),
+        ));
+    }
+    check_trace_length(trace_length::<Rescue4x14>(iterations), &options)?;
+    check_blowup_factor(&options, Rescue4x14::CONSTRAINT_DEGREE)?;
+
+    let trace = build_trace::<Rescue4x14>(seed, iterations);
+    let prover = DoWorkProver::<Rescue4x14, H>::new(options, iterations);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace)?;
+    Ok((pub_inputs, proof))
+}
+
+/// Generates a proof for the hash chain using the specified commitment hash function, and
+/// returns it in a bundle which records this hash function.
+pub fn prove_do_work_bundle(
+    seed: [BaseElement; 2],
+    iterations: usize,
+    hash_fn: HashFunction,
+    options: ProofOptions,
+) -> Result<ProofBundle, Error> {
+    let (pub_inputs, proof) = match hash_fn {
+        HashFunction::Blake3_256 => prove_do_work_with::<Blake3>(seed, iterations, options),
+        HashFunction::Blake3_192 => {
+            prove_do_work_with::<Blake3_192<BaseElement>>(seed, iterations, options)
+        },
+        HashFunction::Sha3_256 => {
+            prove_do_work_with::<Sha3_256<BaseElement>>(seed, iterations, options)
+        },
+    }?;
+    Ok(ProofBundle::with_hash_fn(hash_fn, pub_inputs, proof))
+}
+
+/// Verifies that `proof` attests to the hash chain described by `pub_inputs`, and returns the
+/// security level of the proof.
+///
+/// Proofs are accepted under the default [SecurityPolicy].
+pub fn verify_do_work(pub_inputs: PublicInputs, proof: Proof) -> Result<SecurityLevel, Error> {
+    verify_do_work_with::<Blake3>(pub_inputs, proof, &SecurityPolicy::default())
+}
+
+/// Same as [verify_do_work], but for a proof committed to using the hash function `H` and
+/// accepted under the specified security policy.
+pub fn verify_do_work_with<H: CommitmentHasher>(
+    pub_inputs: PublicInputs,
+    proof: Proof,
+    policy: &SecurityPolicy,
+) -> Result<SecurityLevel, Error> {
+    check_pub_inputs(&pub_inputs, &proof)?;
+    verify_with_policy::<TrainAir, H>(proof, pub_inputs, policy)
+}
+
+/// Verifies the proof in `bundle` using the commitment hash function recorded in the bundle.
+pub fn verify_do_work_bundle(
+    bundle: ProofBundle,
+    policy: &SecurityPolicy,
+) -> Result<SecurityLevel, Error> {
+    let ProofBundle { hash_fn, pub_inputs, proof } = bundle;
+    match hash_fn {
+        HashFunction::Blake3_256 => verify_do_work_with::<Blake3>(pub_inputs, proof, policy),
+        HashFunction::Blake3_192 => {
+            verify_do_work_with::<Blake3_192<BaseElement>>(pub_inputs, proof, policy)
+        },
+        HashFunction::Sha3_256 => {
+            verify_do_work_with::<Sha3_256<BaseElement>>(pub_inputs, proof, policy)
+        },
+    }
+}
+
+/// Verifies a serialized proof bundle for any of the AIRs defined in this crate, and returns
+/// the AIR together with the security level of the proof.
+///
+/// Proofs of every AIR are accepted under the specified security policy; all proofs other than
+/// hash chain proofs must be committed to using Blake3_256.
+pub fn verify_bundle(
+    bytes: &[u8],
+    policy: &SecurityPolicy,
+) -> Result<(AirId, SecurityLevel), Error> {
+    let header = BundleHeader::from_bytes(bytes)?;
+    if header.air != AirId::DoWork && header.hash_fn != HashFunction::Blake3_256 {
+        return Err(BundleError::UnsupportedHashFunction(header.air, header.hash_fn).into());
+    }
+
+    let level = match header.air {
+        AirId::DoWork => verify_do_work_bundle(ProofBundle::from_bytes(bytes)?, policy)?,
+        AirId::Sponge => {
+            let bundle = ProofBundle::<sponge::SpongePublicInputs>::from_bytes(bytes)?;
+            sponge::verify_sponge_with(bundle.pub_inputs, bundle.proof, policy)?
+        },
+        AirId::Merkle => {
+            let bundle = ProofBundle::<merkle::MerklePublicInputs>::from_bytes(bytes)?;
+            merkle::verify_membership_with(bundle.pub_inputs, bundle.proof, policy)?
+        },
+        AirId::HiddenSeed => {
+            let bundle = ProofBundle::<hiding::HiddenSeedPublicInputs>::from_bytes(bytes)?;
+            hiding::verify_hidden_seed_with(bundle.pub_inputs, bundle.proof, policy)?
+        },
+        AirId::Batch => {
+            let bundle = ProofBundle::<batch::BatchPublicInputs>::from_bytes(bytes)?;
+            batch::verify_batch_with(bundle.pub_inputs, bundle.proof, policy)?
+        },
+    };
+    Ok((header.air, level))
+}
+
+/// Makes sure the public inputs describe a computation which fits into the trace of the proof;
+/// [TrainAir] cannot be instantiated otherwise.
+fn check_pub_inputs(pub_inputs: &PublicInputs, proof: &Proof) -> Result<(), Error> {
+    let trace_info = proof.trace_info();
+    if trace_info.width() != TRACE_WIDTH {
+        return Err(Error::PublicInputMismatch(format!(
+            "expected trace width {TRACE_WIDTH}, but proof trace width is {}",
+            trace_info.width()
+        )));
+    }
+    if pub_inputs.iterations == 0 {
+        return Err(Error::PublicInputMismatch(
+            "number of iterations must be greater than zero".to_string(),
+        ));
+    }
+    if result_step::<Rescue4x14>(pub_inputs.iterations) >= trace_info.length() {
+        return Err(Error::PublicInputMismatch(format!(
+            "trace of length {} cannot hold {} iterations",
+            trace_info.length(),
+            pub_inputs.iterations
+        )));
+    }
+    check_blowup_factor(proof.options(), Rescue4x14::CONSTRAINT_DEGREE)
+}
 
+/// Makes sure the blowup factor can accommodate transition constraints of the specified degree,
+/// where every periodic column a constraint depends on adds one to its degree; AIRs cannot be
+/// instantiated otherwise.
+pub(crate) fn check_blowup_factor(
+    options: &ProofOptions,
+    constraint_degree: usize,
+) -> Result<(), Error> {
+    let min_blowup_factor =
+        constraint_degree.next_power_of_two().max(ProofOptions::MIN_BLOWUP_FACTOR);
+    let blowup_factor = options.blowup_factor();
+    if blowup_factor < min_blowup_factor {
+        return Err(Error::PublicInputMismatch(format!(
+            "blowup factor must be at least {min_blowup_factor} for constraints of degree \
+             {constraint_degree}, but was {blowup_factor}"
+        )));
+    }
+    Ok(())
+}
 
+/// Makes sure a trace of the specified length, where None stands for a length which does not
+/// fit into usize, can be proven using the specified options: its low-degree extension must fit
+/// into the largest multiplicative subgroup of the field, or provers panic when building it.
+pub(crate) fn check_trace_length(
+    trace_length: Option<usize>,
+    options: &ProofOptions,
+) -> Result<(), Error> {
+    let blowup_factor = options.blowup_factor();
+    let max_lde_domain_size = 1_u128 << BaseElement::TWO_ADICITY;
+    match trace_length {
+        Some(length) if length as u128 * blowup_factor as u128 <= max_lde_domain_size => Ok(()),
+        _ => Err(Error::PublicInputMismatch(format!(
+            "computation does not fit into a trace whose low-degree extension by a factor of \
+             {blowup_factor} has at most 2^{} steps",
+            BaseElement::TWO_ADICITY
+        ))),
+    }
+}
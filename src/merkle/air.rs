@@ -0,0 +1,133 @@
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+use super::{MerklePublicInputs, BIT_COLUMN, TRACE_WIDTH};
+use crate::utils::permutation::AlgebraicPermutation;
+use crate::utils::rescue::{Rescue6x7, CYCLE_LENGTH, STATE_WIDTH};
+use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
+
+// MERKLE AIR
+// ================================================================================================
+
+/// AIR for the authentication path of a secret leaf in a Merkle tree with a public root.
+///
+/// Transition constraints are enforced only up to the root; the remaining rows of the trace
+/// hold random values.
+pub struct MerkleAir {
+    context: AirContext<BaseElement>,
+    pub_inputs: MerklePublicInputs,
+}
+
+impl Air for MerkleAir {
+    type BaseField = BaseElement;
+    type PublicInputs = MerklePublicInputs;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: MerklePublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        assert!(pub_inputs.depth > 0, "tree depth must be greater than zero");
+        assert!(
+            pub_inputs.root_step() < trace_info.length(),
+            "trace of length {} cannot hold a path of depth {}",
+            trace_info.length(),
+            pub_inputs.depth
+        );
+
+        let cycles = vec![CYCLE_LENGTH, trace_info.length()];
+        let mut degrees = vec![
+            TransitionConstraintDegree::with_cycles(Rescue6x7::CONSTRAINT_DEGREE, cycles);
+            STATE_WIDTH
+        ];
+        degrees.push(TransitionConstraintDegree::with_cycles(2, vec![trace_info.length()]));
+
+        // the capacity must be zero at the start, and the root must be at the end of the path
+        let num_assertions = 4;
+
+        MerkleAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            pub_inputs,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        // expected state width is equal to the width of the Rescue state plus the bit column
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // split periodic values into hash_flag, the mask of constrained steps and Rescue round
+        // constants
+        let hash_flag = periodic_values[0];
+        let enabled = periodic_values[1];
+        let ark = &periodic_values[2..];
+
+        // when hash_flag = 1, constraints for Rescue round are enforced
+        let (state, next_state) = (&current[..STATE_WIDTH], &next[..STATE_WIDTH]);
+        Rescue6x7::enforce_round(result, state, next_state, ark, enabled * hash_flag);
+
+        // when hash_flag = 0, the computed node is moved into the half of the rate selected by
+        // the direction bit of the next level
+        let copy_flag = not(hash_flag);
+        enforce_node_copy(result, current, next, enabled * copy_flag);
+
+        // direction bits must be binary on all steps up to the root
+        result[BIT_COLUMN] = enabled * is_binary(current[BIT_COLUMN]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // the capacity must be zero when the leaf is merged with its sibling, and the node
+        // computed at the end of the last cycle must be equal to the root
+        let [r0, r1] = self.pub_inputs.root.to_elements();
+        let root_step = self.pub_inputs.root_step();
+        vec![
+            Assertion::single(4, 0, BaseElement::ZERO),
+            Assertion::single(5, 0, BaseElement::ZERO),
+            Assertion::single(0, root_step, r0),
+            Assertion::single(1, root_step, r1),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        // transition constraints are enforced for all steps before the root; the transition
+        // from the root into the first random row is not constrained
+        let mut enabled = vec![BaseElement::ZERO; self.trace_length()];
+        enabled[..self.pub_inputs.root_step()].fill(BaseElement::ONE);
+
+        let mut result = vec![Rescue6x7::get_round_mask(), enabled];
+        result.append(&mut Rescue6x7::get_round_constants());
+        result
+    }
+}
+
+// HELPER EVALUATORS
+// ------------------------------------------------------------------------------------------------
+
+/// when flag = 1, enforces that the next state of the computation is defined like so:
+/// - when the next direction bit is 0, the first two registers are equal to the values from the
+///   previous step; otherwise, the third and fourth registers are equal to these values
+/// - the capacity registers are equal to 0
+fn enforce_node_copy<E: FieldElement>(result: &mut [E], current: &[E], next: &[E], flag: E) {
+    let bit = next[BIT_COLUMN];
+    let not_bit = not(bit);
+    result.agg_constraint(0, flag, not_bit * are_equal(current[0], next[0]));
+    result.agg_constraint(1, flag, not_bit * are_equal(current[1], next[1]));
+    result.agg_constraint(2, flag, bit * are_equal(current[0], next[2]));
+    result.agg_constraint(3, flag, bit * are_equal(current[1], next[3]));
+    result.agg_constraint(4, flag, is_zero(next[4]));
+    result.agg_constraint(5, flag, is_zero(next[5]));
+}
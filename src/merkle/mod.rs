@@ -0,0 +1,273 @@
+//! Proves that a leaf belongs to a Merkle tree with a public root.
+//!
+//! The tree is built with [Rescue128::merge]. The execution trace consists of [STATE_WIDTH]
+//! columns holding the Rescue state and one column holding the path direction bit. Every
+//! [CYCLE_LENGTH]-step cycle merges the current node with its sibling: the first
+//! [NUM_ROUNDS] steps apply the permutation rounds, and on the last step the resulting node is
+//! moved into the left or right half of the rate depending on the next direction bit.
+//!
+//! Only the root and the depth of the tree are public. To keep the values opened by the verifier
+//! from leaking the leaf, its siblings or the direction bits, the trace is extended by random
+//! rows after the root in the same way as in [hiding](crate::hiding), and transition constraints
+//! are switched off for these rows.
+
+use rand::Rng;
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use winterfell::{
+    crypto::Hasher,
+    math::{fields::f128::BaseElement, FieldElement, StarkField, ToElements},
+    Proof, ProofOptions, Prover, TraceTable,
+};
+
+use crate::hiding::blinding_steps;
+use crate::utils::permutation::AlgebraicPermutation;
+use crate::utils::rescue::{Hash, Rescue128, Rescue6x7, CYCLE_LENGTH, NUM_ROUNDS, STATE_WIDTH};
+use crate::security::verify_with_policy;
+use crate::{
+    check_blowup_factor, check_trace_length, AirId, Blake3, BundlePublicInputs, Error,
+    SecurityLevel, SecurityPolicy,
+};
+
+mod air;
+pub use air::MerkleAir;
+
+mod prover;
+pub use prover::MerkleProver;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of columns in the execution trace: the Rescue state followed by the direction bit.
+pub const TRACE_WIDTH: usize = STATE_WIDTH + 1;
+
+/// Index of the column holding the path direction bit.
+pub const BIT_COLUMN: usize = STATE_WIDTH;
+
+/// Maximum supported depth of the tree.
+pub const MAX_DEPTH: usize = 64;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MerklePublicInputs {
+    /// Root of the Merkle tree.
+    pub root: Hash,
+    /// Depth of the Merkle tree, i.e. the length of the authentication path.
+    pub depth: usize,
+}
+
+impl MerklePublicInputs {
+    /// Returns the trace step at which the root is located.
+    pub fn root_step(&self) -> usize {
+        self.depth * CYCLE_LENGTH - 1
+    }
+}
+
+impl ToElements<BaseElement> for MerklePublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.root.to_elements().to_vec();
+        result.push(BaseElement::from(self.depth as u64));
+        result
+    }
+}
+
+impl Serializable for MerklePublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.root.write_into(target);
+        target.write_u8(self.depth as u8);
+    }
+}
+
+impl Deserializable for MerklePublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let root = Hash::read_from(source)?;
+        let depth = source.read_u8()? as usize;
+        if depth == 0 || depth > MAX_DEPTH {
+            return Err(DeserializationError::InvalidValue(format!(
+                "tree depth must be between 1 and {MAX_DEPTH}, but was {depth}"
+            )));
+        }
+        Ok(MerklePublicInputs { root, depth })
+    }
+}
+
+impl BundlePublicInputs for MerklePublicInputs {
+    const AIR_ID: AirId = AirId::Merkle;
+}
+
+// TRACE BUILDER
+// ================================================================================================
+
+/// Returns the length of the execution trace needed to authenticate a path of `depth` nodes
+/// followed by at least `blinding_steps` random rows, or None if the length does not fit into
+/// usize.
+///
+/// See [blinding_steps](crate::hiding::blinding_steps) for the number of random rows needed to
+/// hide the path from the verifier.
+pub fn trace_length(depth: usize, blinding_steps: usize) -> Option<usize> {
+    let num_cycles = depth.checked_add(blinding_steps.div_ceil(CYCLE_LENGTH))?;
+    num_cycles.checked_next_power_of_two()?.checked_mul(CYCLE_LENGTH)
+}
+
+/// Builds an execution trace computing the root of a Merkle tree from a leaf at the specified
+/// `index` and its authentication `path`, ordered from the leaf level up; all rows after the
+/// root are filled with values drawn from `rng`.
+///
+/// # Panics
+/// Panics if the path is empty or longer than [MAX_DEPTH], if `index` does not fit into a tree
+/// of depth `path.len()`, or if the length of the trace does not fit into usize.
+pub fn build_trace<R: Rng>(
+    leaf: Hash,
+    index: u64,
+    path: &[Hash],
+    blinding_steps: usize,
+    rng: &mut R,
+) -> TraceTable<BaseElement> {
+    let depth = path.len();
+    assert!(depth > 0 && depth <= MAX_DEPTH, "tree depth must be between 1 and {MAX_DEPTH}");
+    assert!(depth == 64 || index >> depth == 0, "index {index} is too big for depth {depth}");
+
+    let trace_length = trace_length(depth, blinding_steps).expect("too many blinding steps");
+    let root_step = depth * CYCLE_LENGTH - 1;
+    let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+
+    trace.fill(
+        |state| {
+            // initialize the first state with the leaf and its sibling
+            let bit = index & 1;
+            init_level(state, leaf, path[0], bit);
+        },
+        |step, state| {
+            // execute the transition function for all steps up to the root, and fill the
+            // remaining rows with random values
+            //
+            // for the first 7 steps in every cycle, compute a single round of Rescue
+            // permutation; on the last step, merge the result with the sibling on the
+            // next level
+            let cycle_pos = step % CYCLE_LENGTH;
+            if step >= root_step {
+                state.fill_with(|| BaseElement::new(rng.gen_range(0..BaseElement::MODULUS)));
+            } else if cycle_pos < NUM_ROUNDS {
+                Rescue6x7::apply_round(&mut state[..STATE_WIDTH], cycle_pos);
+                state[BIT_COLUMN] = filler_bit(state);
+            } else {
+                let level = step / CYCLE_LENGTH + 1;
+                let node = Hash::new(state[0], state[1]);
+                init_level(state, node, path[level], (index >> level) & 1);
+            }
+        },
+    );
+
+    debug_assert_eq!(
+        compute_root(leaf, index, path).to_elements(),
+        [trace.get(0, root_step), trace.get(1, root_step)],
+        "trace does not compute the root of the tree"
+    );
+
+    trace
+}
+
+/// Computes the root of a Merkle tree natively from a leaf at the specified `index` and its
+/// authentication `path`, ordered from the leaf level up.
+pub fn compute_root(leaf: Hash, index: u64, path: &[Hash]) -> Hash {
+    let mut node = leaf;
+    for (level, &sibling) in path.iter().enumerate() {
+        node = if (index >> level) & 1 == 0 {
+            Rescue128::merge(&[node, sibling])
+        } else {
+            Rescue128::merge(&[sibling, node])
+        };
+    }
+    node
+}
+
+/// Returns the value of the direction bit column on a step where no direction bit is read.
+///
+/// Direction bits are read only on the first step of a cycle, and the AIR only requires the
+/// column to be binary elsewhere. The column must not follow a low-degree pattern though: when
+/// it is constant, e.g. for the leftmost leaf, or periodic, the binary constraint has a lower
+/// degree than declared, and the prover rejects the trace in debug builds. The low bit of a
+/// capacity element of the Rescue state after a round is as good as random for this purpose,
+/// and keeps the rows up to the root deterministic.
+fn filler_bit(state: &[BaseElement]) -> BaseElement {
+    BaseElement::from(state[STATE_WIDTH - 1].as_int() as u64 & 1)
+}
+
+/// Places the node and its sibling into the rate in the order defined by the direction bit,
+/// and resets the capacity.
+fn init_level(state: &mut [BaseElement], node: Hash, sibling: Hash, bit: u64) {
+    let (left, right) = if bit == 0 { (node, sibling) } else { (sibling, node) };
+    state[..2].copy_from_slice(&left.to_elements());
+    state[2..4].copy_from_slice(&right.to_elements());
+    state[4..STATE_WIDTH].fill(BaseElement::ZERO);
+    state[BIT_COLUMN] = BaseElement::from(bit);
+}
+
+// PROVE AND VERIFY
+// ================================================================================================
+
+/// Generates a STARK proof attesting that the prover knows a leaf and an authentication path
+/// leading to the root of a Merkle tree; only the root and the depth of the tree are revealed.
+pub fn prove_membership(
+    leaf: Hash,
+    index: u64,
+    path: &[Hash],
+    options: ProofOptions,
+) -> Result<(MerklePublicInputs, Proof), Error> {
+    let depth = path.len();
+    if depth == 0 || depth > MAX_DEPTH {
+        return Err(Error::PublicInputMismatch(format!(
+            "tree depth must be between 1 and {MAX_DEPTH}, but was {depth}"
+        )));
+    }
+    if depth < 64 && index >> depth != 0 {
+        return Err(Error::PublicInputMismatch(format!(
+            "leaf index {index} does not fit into a tree of depth {depth}"
+        )));
+    }
+    let blinding_steps = blinding_steps(&options);
+    check_trace_length(trace_length(depth, blinding_steps), &options)?;
+    check_blowup_factor(&options, Rescue6x7::CONSTRAINT_DEGREE + 1)?;
+
+    let trace = build_trace(leaf, index, path, blinding_steps, &mut rand::thread_rng());
+    let prover = MerkleProver::new(options, depth);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace)?;
+    Ok((pub_inputs, proof))
+}
+
+/// Verifies that `proof` attests to the membership of a secret leaf in the tree described by
+/// `pub_inputs`, and returns the security level of the proof.
+pub fn verify_membership(
+    pub_inputs: MerklePublicInputs,
+    proof: Proof,
+) -> Result<SecurityLevel, Error> {
+    verify_membership_with(pub_inputs, proof, &SecurityPolicy::default())
+}
+
+/// Same as [verify_membership], but accepts the proof under the specified security policy.
+pub fn verify_membership_with(
+    pub_inputs: MerklePublicInputs,
+    proof: Proof,
+    policy: &SecurityPolicy,
+) -> Result<SecurityLevel, Error> {
+    let trace_info = proof.trace_info();
+    if trace_info.width() != TRACE_WIDTH {
+        return Err(Error::PublicInputMismatch(format!(
+            "expected trace width {TRACE_WIDTH}, but proof trace width is {}",
+            trace_info.width()
+        )));
+    }
+    if pub_inputs.depth == 0 || pub_inputs.root_step() >= trace_info.length() {
+        return Err(Error::PublicInputMismatch(format!(
+            "trace of length {} cannot hold a path of depth {}",
+            trace_info.length(),
+            pub_inputs.depth
+        )));
+    }
+    // the constraints also depend on a periodic column switching them off after the root
+    check_blowup_factor(proof.options(), Rescue6x7::CONSTRAINT_DEGREE + 1)?;
+
+    verify_with_policy::<MerkleAir, Blake3>(proof, pub_inputs, policy)
+}
@@ -0,0 +1,66 @@
+use winter_air::PartitionOptions;
+use winterfell::{
+    crypto::{DefaultRandomCoin, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    matrix::ColMatrix,
+    AuxRandElements, DefaultConstraintEvaluator, DefaultTraceLde, ProofOptions, Prover,
+    StarkDomain, TraceInfo, TracePolyTable, TraceTable,
+};
+
+use super::{MerkleAir, MerklePublicInputs};
+use crate::utils::rescue::Hash;
+use crate::Blake3;
+
+pub struct MerkleProver {
+    options: ProofOptions,
+    depth: usize,
+}
+
+impl MerkleProver {
+    /// Returns a prover for membership in a Merkle tree of the specified depth.
+    pub fn new(options: ProofOptions, depth: usize) -> Self {
+        Self { options, depth }
+    }
+}
+
+impl Prover for MerkleProver {
+    type BaseField = BaseElement;
+    type Air = MerkleAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+    type VC = MerkleTree<Self::HashFn>;
+    type RandomCoin = DefaultRandomCoin<Blake3>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Blake3, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, MerkleAir, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> MerklePublicInputs {
+        let mut pub_inputs = MerklePublicInputs { root: Hash::default(), depth: self.depth };
+        let root_step = pub_inputs.root_step();
+        pub_inputs.root = Hash::new(trace.get(0, root_step), trace.get(1, root_step));
+        pub_inputs
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_option: PartitionOptions,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain, partition_option)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        air: &'a MerkleAir,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: winterfell::ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
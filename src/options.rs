@@ -0,0 +1,156 @@
+use winterfell::{
+    math::{
+        fields::{f128::BaseElement, CubeExtension, QuadExtension},
+        StarkField,
+    },
+    FieldExtension, ProofOptions,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Conjectured security level targeted by [production_options], in bits.
+pub const PRODUCTION_SECURITY_LEVEL: u32 = 128;
+
+const BLOWUP_FACTOR: usize = 16;
+const GRINDING_FACTOR: u32 = 16;
+const FOLDING_FACTOR: usize = 8;
+const REMAINDER_DEGREE: usize = 127;
+
+// PRODUCTION PROFILE
+// ================================================================================================
+
+/// Returns proof options providing [PRODUCTION_SECURITY_LEVEL] bits of conjectured security for
+/// a trace of `trace_length` steps.
+pub fn production_options(trace_length: usize) -> ProofOptions {
+    options_for_security(trace_length, PRODUCTION_SECURITY_LEVEL)
+}
+
+/// Returns proof options providing at least `security_level` bits of conjectured security for a
+/// trace of `trace_length` steps.
+///
+/// The number of queries is chosen to reach the security level, and the composition polynomial
+/// is evaluated over the smallest supported field extension which is large enough for it. The
+/// security of a proof is also bounded by the collision resistance of the commitment hash
+/// function, which is 128 bits for Blake3_256.
+pub fn options_for_security(trace_length: usize, security_level: u32) -> ProofOptions {
+    // conjectured security is one bit less than the security of the weakest component
+    let target = security_level + 1;
+
+    let bits_per_query = BLOWUP_FACTOR.ilog2();
+    let num_queries = target.saturating_sub(GRINDING_FACTOR).div_ceil(bits_per_query).clamp(1, 255);
+
+    let lde_domain_size = trace_length
+        .checked_next_power_of_two()
+        .map_or(usize::MAX, |length| length.saturating_mul(BLOWUP_FACTOR));
+    let field_extension = select_field_extension(lde_domain_size, target);
+
+    ProofOptions::new(
+        num_queries as usize,
+        BLOWUP_FACTOR,
+        GRINDING_FACTOR,
+        field_extension,
+        FOLDING_FACTOR,
+        REMAINDER_DEGREE,
+    )
+}
+
+/// Returns true if the field extension is supported for the f128 field.
+///
+/// The cubic extension of f128 is not implemented, and proofs requesting it are rejected.
+pub fn is_extension_supported(extension: FieldExtension) -> bool {
+    match extension {
+        FieldExtension::None => true,
+        FieldExtension::Quadratic => QuadExtension::<BaseElement>::is_supported(),
+        FieldExtension::Cubic => CubeExtension::<BaseElement>::is_supported(),
+    }
+}
+
+// VALIDATORS
+// ================================================================================================
+
+/// Returns proof options with the specified parameters; unlike [ProofOptions::new], invalid
+/// parameters are reported as an error instead of causing a panic.
+pub fn validate_proof_options(
+    num_queries: usize,
+    blowup_factor: usize,
+    grinding_factor: u32,
+    field_extension: FieldExtension,
+    folding_factor: usize,
+    remainder_degree: usize,
+) -> Result<ProofOptions, String> {
+    if !is_extension_supported(field_extension) {
+        return Err(format!(
+            "field extension of degree {} is not supported for the f128 field",
+            field_extension.degree()
+        ));
+    }
+    Ok(ProofOptions::new(
+        check_num_queries(num_queries)?,
+        check_blowup_factor(blowup_factor)?,
+        check_grinding_factor(grinding_factor)?,
+        field_extension,
+        check_folding_factor(folding_factor)?,
+        check_remainder_degree(remainder_degree)?,
+    ))
+}
+
+pub(crate) fn check_num_queries(num_queries: usize) -> Result<usize, String> {
+    if num_queries == 0 || num_queries > 255 {
+        return Err(format!("number of queries must be between 1 and 255, but was {num_queries}"));
+    }
+    Ok(num_queries)
+}
+
+pub(crate) fn check_blowup_factor(blowup_factor: usize) -> Result<usize, String> {
+    if !blowup_factor.is_power_of_two() || !(2..=128).contains(&blowup_factor) {
+        return Err(format!(
+            "blowup factor must be a power of two between 2 and 128, but was {blowup_factor}"
+        ));
+    }
+    Ok(blowup_factor)
+}
+
+pub(crate) fn check_grinding_factor(grinding_factor: u32) -> Result<u32, String> {
+    if grinding_factor > 32 {
+        return Err(format!("grinding factor cannot be greater than 32, but was {grinding_factor}"));
+    }
+    Ok(grinding_factor)
+}
+
+pub(crate) fn check_folding_factor(folding_factor: usize) -> Result<usize, String> {
+    if !matches!(folding_factor, 2 | 4 | 8 | 16) {
+        return Err(format!("FRI folding factor must be 2, 4, 8 or 16, but was {folding_factor}"));
+    }
+    Ok(folding_factor)
+}
+
+pub(crate) fn check_remainder_degree(remainder_degree: usize) -> Result<usize, String> {
+    if remainder_degree > 255 || !(remainder_degree + 1).is_power_of_two() {
+        return Err(format!(
+            "FRI remainder degree must be one less than a power of two and at most 255, but was \
+             {remainder_degree}"
+        ));
+    }
+    Ok(remainder_degree)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the smallest supported extension providing `target` bits of field security over an
+/// LDE domain of the specified size, or the largest supported extension if none does.
+fn select_field_extension(lde_domain_size: usize, target: u32) -> FieldExtension {
+    let extensions = [FieldExtension::None, FieldExtension::Quadratic, FieldExtension::Cubic];
+    let supported = extensions.into_iter().filter(|&ext| is_extension_supported(ext));
+
+    let mut selected = FieldExtension::None;
+    for extension in supported {
+        selected = extension;
+        let field_bits = BaseElement::MODULUS_BITS * extension.degree();
+        if field_bits.saturating_sub(lde_domain_size.ilog2()) >= target {
+            break;
+        }
+    }
+    selected
+}
@@ -0,0 +1,69 @@
+//! Parsers for values given as strings on the command line or in JSON files.
+
+use winterfell::{
+    math::{fields::f128::BaseElement, StarkField},
+    FieldExtension,
+};
+
+use crate::{is_extension_supported, HashFunction};
+
+// VALUE PARSERS
+// ================================================================================================
+
+/// Parses a field element from a decimal or 0x-prefixed hexadecimal string; values which are
+/// not smaller than the field modulus are rejected.
+///
+/// Only canonical strings are accepted: a leading `+` and leading zeros are rejected, so that a
+/// serialized element is read back from exactly the string it was written as.
+pub fn parse_element(value: &str) -> Result<BaseElement, String> {
+    let (digits, radix) = match value.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (value, 10),
+    };
+    if digits.starts_with('+') || (digits.len() > 1 && digits.starts_with('0')) {
+        return Err(format!(
+            "'{value}' is not a valid field element: signs and leading zeros are not allowed"
+        ));
+    }
+    let parsed = u128::from_str_radix(digits, radix)
+        .map_err(|err| format!("'{value}' is not a valid field element: {err}"))?;
+
+    if parsed >= BaseElement::MODULUS {
+        return Err(format!(
+            "'{value}' is not smaller than the field modulus {}",
+            BaseElement::MODULUS
+        ));
+    }
+    Ok(BaseElement::new(parsed))
+}
+
+/// Parses a commitment hash function from its name.
+pub fn parse_hash_function(value: &str) -> Result<HashFunction, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "blake3-256" | "blake3_256" => Ok(HashFunction::Blake3_256),
+        "blake3-192" | "blake3_192" => Ok(HashFunction::Blake3_192),
+        "sha3-256" | "sha3_256" => Ok(HashFunction::Sha3_256),
+        _ => Err(format!(
+            "'{value}' is not a hash function; expected blake3-256, blake3-192 or sha3-256"
+        )),
+    }
+}
+
+/// Parses a field extension from its name or degree; extensions which are not supported for the
+/// f128 field are rejected.
+pub fn parse_field_extension(value: &str) -> Result<FieldExtension, String> {
+    let extension = match value.to_ascii_lowercase().as_str() {
+        "none" | "1" => FieldExtension::None,
+        "quadratic" | "2" => FieldExtension::Quadratic,
+        "cubic" | "3" => FieldExtension::Cubic,
+        _ => {
+            return Err(format!(
+                "'{value}' is not a field extension; expected none, quadratic or cubic"
+            ))
+        },
+    };
+    if !is_extension_supported(extension) {
+        return Err(format!("'{value}' field extension is not supported for the f128 field"));
+    }
+    Ok(extension)
+}
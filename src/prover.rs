@@ -1,3 +1,5 @@
+use core::marker::PhantomData;
+
 use winter_air::PartitionOptions;
 use winterfell::{
     crypto::{DefaultRandomCoin, MerkleTree},
@@ -5,37 +7,47 @@ use winterfell::{
     matrix::ColMatrix,
     AuxRandElements,
     DefaultConstraintEvaluator, DefaultTraceLde, ProofOptions, Prover,
-    StarkDomain, Trace, TraceInfo, TracePolyTable, TraceTable
+    StarkDomain, TraceInfo, TracePolyTable, TraceTable
 };
 
-use crate::{TrainAir, PublicInputs, Blake3};
+use crate::{
+    result_step, AlgebraicPermutation, Blake3, CommitmentHasher, PublicInputs, Rescue4x14,
+    TrainAir,
+};
 
-pub struct DoWorkProver {
+/// Prover for a chain of hashes computed with the permutation `P`; the trace and constraint
+/// evaluations are committed to using the hash function `H`.
+pub struct DoWorkProver<P: AlgebraicPermutation = Rescue4x14, H: CommitmentHasher = Blake3> {
     options: ProofOptions,
+    iterations: usize,
+    _permutation: PhantomData<P>,
+    _hasher: PhantomData<H>,
 }
 
-impl DoWorkProver {
-    pub fn new(options: ProofOptions) -> Self {
-        Self { options }
+impl<P: AlgebraicPermutation, H: CommitmentHasher> DoWorkProver<P, H> {
+    /// Returns a prover for a hash chain of `iterations` chained hashes.
+    pub fn new(options: ProofOptions, iterations: usize) -> Self {
+        Self { options, iterations, _permutation: PhantomData, _hasher: PhantomData }
     }
 }
 
-impl Prover for DoWorkProver {
+impl<P: AlgebraicPermutation, H: CommitmentHasher> Prover for DoWorkProver<P, H> {
     type BaseField = BaseElement;
-    type Air = TrainAir;
+    type Air = TrainAir<P>;
     type Trace = TraceTable<BaseElement>;
-    type HashFn = Blake3;
+    type HashFn = H;
     type VC = MerkleTree<Self::HashFn>;
-    type RandomCoin = DefaultRandomCoin<Blake3>;
-    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Blake3, Self::VC>;
+    type RandomCoin = DefaultRandomCoin<H>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, H, Self::VC>;
     type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
-        DefaultConstraintEvaluator<'a, TrainAir, E>;
+        DefaultConstraintEvaluator<'a, TrainAir<P>, E>;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
-        let last_step = trace.length() - 1;
+        let result_step = result_step::<P>(self.iterations);
         PublicInputs {
             seed: [trace.get(0, 0), trace.get(1, 0)],
-            result: [trace.get(0, last_step), trace.get(1, last_step)],
+            result: [trace.get(0, result_step), trace.get(1, result_step)],
+            iterations: self.iterations,
         }
     }
 
@@ -51,7 +63,7 @@ impl Prover for DoWorkProver {
 
     fn new_evaluator<'a, E: FieldElement<BaseField = BaseElement>>(
         &self,
-        air: &'a TrainAir,
+        air: &'a TrainAir<P>,
         aux_rand_elements: Option<AuxRandElements<E>>,
         composition_coefficients: winterfell::ConstraintCompositionCoefficients<E>,
     ) -> Self::ConstraintEvaluator<'a, E> {
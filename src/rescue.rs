@@ -5,7 +5,9 @@
 
 use winterfell::math::{fields::f128::BaseElement, FieldElement};
 
-use crate::utils::{are_equal, EvaluationResult};
+use crate::utils::permutation::{
+    apply_rescue_round, enforce_rescue_round, get_rescue_round_constants, AlgebraicPermutation,
+};
 
 /// The number of rounds is set to 14 to provide 128-bit security level.
 /// computed using algorithm 7 from https://eprint.iacr.org/2020/1143.pdf
@@ -19,149 +21,59 @@ pub const CYCLE_LENGTH: usize = 16;
 
 /// Implementation of Rescue hash function with a 4 element state and 14 rounds. Accepts a
 /// 2-element input, and returns a 2-element digest.
-// pub fn hash(value: [BaseElement; 2], result: &mut [BaseElement]) {
-//     let mut state = [BaseElement::ZERO; STATE_WIDTH];
-//     state[..2].copy_from_slice(&value);
-//     for i in 0..NUM_ROUNDS {
-//         apply_round(&mut state, i);
-//     }
-//     result.copy_from_slice(&state[..2]);
-// }
-
-// TRACE
-// ================================================================================================
-
-pub fn apply_round(state: &mut [BaseElement], step: usize) {
-    // determine which round constants to use
-    let ark = ARK[step % CYCLE_LENGTH];
-
-    // apply first half of Rescue round
-    apply_sbox(state);
-    apply_mds(state);
-    add_constants(state, &ark, 0);
-
-    // apply second half of Rescue round
-    apply_inv_sbox(state);
-    apply_mds(state);
-    add_constants(state, &ark, STATE_WIDTH);
-}
-
-// CONSTRAINTS
-// ================================================================================================
-
-/// when flag = 1, enforces constraints for a single round of Rescue hash functions
-pub fn enforce_round<E: FieldElement + From<BaseElement>>(
-    result: &mut [E],
-    current: &[E],
-    next: &[E],
-    ark: &[E],
-    flag: E,
-) {
-    // compute the state that should result from applying the first half of Rescue round
-    // to the current state of the computation
-    let mut step1 = [E::ZERO; STATE_WIDTH];
-    step1.copy_from_slice(current);
-    apply_sbox(&mut step1);
-    apply_mds(&mut step1);
-    for i in 0..STATE_WIDTH {
-        step1[i] += ark[i];
-    }
-
-    // compute the state that should result from applying the inverse for the second
-    // half for Rescue round to the next step of the computation
-    let mut step2 = [E::ZERO; STATE_WIDTH];
-    step2.copy_from_slice(next);
-    for i in 0..STATE_WIDTH {
-        step2[i] -= ark[STATE_WIDTH + i];
-    }
-    apply_inv_mds(&mut step2);
-    apply_sbox(&mut step2);
-
-    // make sure that the results are equal
-    for i in 0..STATE_WIDTH {
-        result.agg_constraint(i, flag, are_equal(step2[i], step1[i]));
-    }
+///
+/// This is the hash which [TrainAir](crate::TrainAir) constrains in every cycle of the trace: the
+/// input followed by two zeros is permuted, and the first two elements of the state are returned.
+pub fn hash(value: [BaseElement; 2]) -> [BaseElement; 2] {
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+    state[..2].copy_from_slice(&value);
+    Rescue4x14::apply_permutation(&mut state);
+    [state[0], state[1]]
 }
 
-// ROUND CONSTANTS
-// ================================================================================================
-
-/// Returns Rescue round constants arranged in column-major form.
-pub fn get_round_constants() -> Vec<Vec<BaseElement>> {
-    let mut constants = Vec::new();
-    for _ in 0..(STATE_WIDTH * 2) {
-        constants.push(vec![BaseElement::ZERO; CYCLE_LENGTH]);
-    }
-
-    #[allow(clippy::needless_range_loop)]
-    for i in 0..CYCLE_LENGTH {
-        for j in 0..(STATE_WIDTH * 2) {
-            constants[j][i] = ARK[i][j];
-        }
-    }
-
-    constants
+/// Returns the result of `n` chained hashes starting from `seed`, i.e. `hash(...hash(seed))`;
+/// for `n = 0` the seed itself is returned.
+///
+/// For `n > 0`, this is the value which the trace built by [build_trace](crate::build_trace)
+/// holds in its first two registers at step [result_step](crate::result_step).
+pub fn hash_chain(seed: [BaseElement; 2], n: usize) -> [BaseElement; 2] {
+    (0..n).fold(seed, |value, _| hash(value))
 }
 
-// HELPER FUNCTIONS
+// PERMUTATION
 // ================================================================================================
 
-#[inline(always)]
-#[allow(clippy::needless_range_loop)]
-fn add_constants(state: &mut [BaseElement], ark: &[BaseElement], offset: usize) {
-    for i in 0..STATE_WIDTH {
-        state[i] += ark[offset + i];
-    }
-}
+/// Rescue permutation with a 4 element state, 14 rounds and S-Box power 3.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Rescue4x14;
 
-#[inline(always)]
-#[allow(clippy::needless_range_loop)]
-fn apply_sbox<E: FieldElement>(state: &mut [E]) {
-    for i in 0..STATE_WIDTH {
-        state[i] = state[i].exp(ALPHA.into());
-    }
-}
+impl AlgebraicPermutation for Rescue4x14 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const NUM_ROUNDS: usize = NUM_ROUNDS;
+    const CYCLE_LENGTH: usize = CYCLE_LENGTH;
+    const CONSTRAINT_DEGREE: usize = ALPHA as usize;
 
-#[inline(always)]
-#[allow(clippy::needless_range_loop)]
-fn apply_inv_sbox(state: &mut [BaseElement]) {
-    for i in 0..STATE_WIDTH {
-        state[i] = state[i].exp(INV_ALPHA);
+    fn apply_round(state: &mut [BaseElement], step: usize) {
+        // determine which round constants to use
+        let ark = &ARK[step % CYCLE_LENGTH];
+        apply_rescue_round::<STATE_WIDTH>(state, ark, ALPHA, INV_ALPHA, &MDS);
     }
-}
 
-#[inline(always)]
-#[allow(clippy::needless_range_loop)]
-fn apply_mds<E: FieldElement + From<BaseElement>>(state: &mut [E]) {
-    let mut result = [E::ZERO; STATE_WIDTH];
-    let mut temp = [E::ZERO; STATE_WIDTH];
-    for i in 0..STATE_WIDTH {
-        for j in 0..STATE_WIDTH {
-            temp[j] = E::from(MDS[i * STATE_WIDTH + j]) * state[j];
-        }
-
-        for j in 0..STATE_WIDTH {
-            result[i] += temp[j];
-        }
+    fn enforce_round<E: FieldElement + From<BaseElement>>(
+        result: &mut [E],
+        current: &[E],
+        next: &[E],
+        ark: &[E],
+        flag: E,
+    ) {
+        enforce_rescue_round::<E, STATE_WIDTH>(
+            result, current, next, ark, flag, ALPHA, &MDS, &INV_MDS,
+        );
     }
-    state.copy_from_slice(&result);
-}
-
-#[inline(always)]
-#[allow(clippy::needless_range_loop)]
-fn apply_inv_mds<E: FieldElement + From<BaseElement>>(state: &mut [E]) {
-    let mut result = [E::ZERO; STATE_WIDTH];
-    let mut temp = [E::ZERO; STATE_WIDTH];
-    for i in 0..STATE_WIDTH {
-        for j in 0..STATE_WIDTH {
-            temp[j] = E::from(INV_MDS[i * STATE_WIDTH + j]) * state[j];
-        }
 
-        for j in 0..STATE_WIDTH {
-            result[i] += temp[j];
-        }
+    fn get_round_constants() -> Vec<Vec<BaseElement>> {
+        get_rescue_round_constants(&ARK)
     }
-    state.copy_from_slice(&result);
 }
 
 // RESCUE CONSTANTS
@@ -0,0 +1,168 @@
+use core::fmt;
+
+use winterfell::{
+    crypto::{
+        hashers::{Blake3_192, Blake3_256, Sha3_256},
+        DefaultRandomCoin, MerkleTree,
+    },
+    math::fields::f128::BaseElement,
+    AcceptableOptions, Air, Proof, ProofOptions,
+};
+
+use crate::{CommitmentHasher, Error, HashFunction};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Conjectured security level which proofs must reach under the default [SecurityPolicy].
+pub const DEFAULT_MIN_SECURITY_LEVEL: u32 = 95;
+
+// TYPES AND INTERFACES
+// ================================================================================================
+
+/// Security level of a proof, in bits.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SecurityLevel {
+    /// Security level based on the conjectured soundness of FRI.
+    pub conjectured: u32,
+    /// Security level based on the proven soundness of FRI.
+    pub proven: u32,
+}
+
+/// Requirements which a proof must satisfy to be accepted by the verifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityPolicy {
+    /// Accept proofs with at least this many bits of conjectured security.
+    MinConjectured(u32),
+    /// Accept proofs with at least this many bits of proven security.
+    MinProven(u32),
+    /// Accept only proofs generated with one of the listed options.
+    AllowList(Vec<ProofOptions>),
+}
+
+/// Represents an error returned when a proof does not satisfy the [SecurityPolicy] of the
+/// verifier; every variant carries the actual security level of the proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SecurityError {
+    /// This error occurs when the conjectured security level is below the required minimum.
+    InsufficientConjectured { required: u32, actual: SecurityLevel },
+    /// This error occurs when the proven security level is below the required minimum.
+    InsufficientProven { required: u32, actual: SecurityLevel },
+    /// This error occurs when the proof options are not in the allow-list.
+    UnacceptableOptions { actual: SecurityLevel },
+}
+
+// SECURITY LEVEL
+// ================================================================================================
+
+impl SecurityLevel {
+    /// Returns the security level of a proof committed to using the hash function `H`.
+    pub fn of<H: CommitmentHasher>(proof: &Proof) -> Self {
+        Self {
+            conjectured: proof.security_level::<H>(true),
+            proven: proof.security_level::<H>(false),
+        }
+    }
+
+    /// Returns the security level of a proof committed to using the specified hash function.
+    pub fn with_hash_fn(hash_fn: HashFunction, proof: &Proof) -> Self {
+        match hash_fn {
+            HashFunction::Blake3_256 => Self::of::<Blake3_256<BaseElement>>(proof),
+            HashFunction::Blake3_192 => Self::of::<Blake3_192<BaseElement>>(proof),
+            HashFunction::Sha3_256 => Self::of::<Sha3_256<BaseElement>>(proof),
+        }
+    }
+}
+
+impl fmt::Display for SecurityLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bits conjectured, {} bits proven", self.conjectured, self.proven)
+    }
+}
+
+// SECURITY POLICY
+// ================================================================================================
+
+impl SecurityPolicy {
+    /// Checks that a proof with the specified options and security level is acceptable.
+    pub fn check(&self, options: &ProofOptions, level: SecurityLevel) -> Result<(), SecurityError> {
+        match self {
+            Self::MinConjectured(required) if level.conjectured < *required => {
+                Err(SecurityError::InsufficientConjectured { required: *required, actual: level })
+            },
+            Self::MinProven(required) if level.proven < *required => {
+                Err(SecurityError::InsufficientProven { required: *required, actual: level })
+            },
+            Self::AllowList(allowed) if !allowed.contains(options) => {
+                Err(SecurityError::UnacceptableOptions { actual: level })
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the equivalent options for the winterfell verifier.
+    fn to_acceptable_options(&self) -> AcceptableOptions {
+        match self {
+            Self::MinConjectured(bits) => AcceptableOptions::MinConjecturedSecurity(*bits),
+            Self::MinProven(bits) => AcceptableOptions::MinProvenSecurity(*bits),
+            Self::AllowList(allowed) => AcceptableOptions::OptionSet(allowed.clone()),
+        }
+    }
+}
+
+impl Default for SecurityPolicy {
+    fn default() -> Self {
+        Self::MinConjectured(DEFAULT_MIN_SECURITY_LEVEL)
+    }
+}
+
+impl fmt::Display for SecurityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InsufficientConjectured { required, actual } => write!(
+                f,
+                "at least {required} bits of conjectured security are required, but the proof \
+                 provides {actual}"
+            ),
+            Self::InsufficientProven { required, actual } => write!(
+                f,
+                "at least {required} bits of proven security are required, but the proof \
+                 provides {actual}"
+            ),
+            Self::UnacceptableOptions { actual } => write!(
+                f,
+                "proof options are not in the list of accepted options; the proof provides \
+                 {actual}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SecurityError {}
+
+// VERIFICATION
+// ================================================================================================
+
+/// Checks the proof against the policy and verifies it with the AIR `A` and the commitment hash
+/// function `H`; returns the security level of the proof.
+pub(crate) fn verify_with_policy<A, H>(
+    proof: Proof,
+    pub_inputs: A::PublicInputs,
+    policy: &SecurityPolicy,
+) -> Result<SecurityLevel, Error>
+where
+    A: Air<BaseField = BaseElement>,
+    H: CommitmentHasher,
+{
+    let level = SecurityLevel::of::<H>(&proof);
+    policy.check(proof.options(), level)?;
+
+    // The number of steps and options are encoded in the proof itself, so we don't need to
+    // pass them explicitly to the verifier.
+    winterfell::verify::<A, H, DefaultRandomCoin<H>, MerkleTree<H>>(
+        proof,
+        pub_inputs,
+        &policy.to_acceptable_options(),
+    )?;
+    Ok(level)
+}
@@ -0,0 +1,171 @@
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+use super::{SpongePublicInputs, TRACE_WIDTH};
+use crate::utils::permutation::AlgebraicPermutation;
+use crate::utils::rescue::{Rescue6x7, CYCLE_LENGTH, RATE_WIDTH, STATE_WIDTH};
+use crate::utils::{are_equal, not, EvaluationResult};
+
+// SPONGE AIR
+// ================================================================================================
+
+pub struct SpongeAir {
+    context: AirContext<BaseElement>,
+    pub_inputs: SpongePublicInputs,
+    digest_step: usize,
+}
+
+impl Air for SpongeAir {
+    type BaseField = BaseElement;
+    type PublicInputs = SpongePublicInputs;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: SpongePublicInputs, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let digest_step = pub_inputs
+            .digest_step()
+            .filter(|&step| step < trace_info.length())
+            .unwrap_or_else(|| {
+                panic!(
+                    "trace of length {} cannot hold a message of {} elements",
+                    trace_info.length(),
+                    pub_inputs.msg_len
+                )
+            });
+
+        // the absorption constraints, including the one depending on the padding flag, are of
+        // lower degree than the round constraints
+        let degrees = vec![
+            TransitionConstraintDegree::with_cycles(
+                Rescue6x7::CONSTRAINT_DEGREE,
+                vec![CYCLE_LENGTH]
+            );
+            STATE_WIDTH
+        ];
+
+        // the initial state must be zero, the last block must be padded, and the digest must
+        // be located at the end of the last cycle
+        let num_assertions = STATE_WIDTH + num_padding_assertions(&pub_inputs) + 2;
+
+        SpongeAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            pub_inputs,
+            digest_step,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        // expected state width is equal to the width of the sponge state plus one block
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // split periodic values into hash_flag, padding flag and Rescue round constants
+        let hash_flag = periodic_values[0];
+        let padding_flag = periodic_values[1];
+        let ark = &periodic_values[2..];
+
+        // when hash_flag = 1, constraints for Rescue round are enforced
+        let (state, next_state) = (&current[..STATE_WIDTH], &next[..STATE_WIDTH]);
+        Rescue6x7::enforce_round(result, state, next_state, ark, hash_flag);
+
+        // when hash_flag = 0, the message block is absorbed into the rate
+        let absorb_flag = not(hash_flag);
+        let block = &current[STATE_WIDTH..];
+        for i in 0..RATE_WIDTH {
+            result.agg_constraint(i, absorb_flag, are_equal(next[i], current[i] + block[i]));
+        }
+
+        // the capacity is carried over, except for the padding flag on the last block
+        result.agg_constraint(
+            RATE_WIDTH,
+            absorb_flag,
+            are_equal(next[RATE_WIDTH], current[RATE_WIDTH]),
+        );
+        result.agg_constraint(
+            STATE_WIDTH - 1,
+            absorb_flag,
+            are_equal(next[STATE_WIDTH - 1], current[STATE_WIDTH - 1] + padding_flag),
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut assertions = Vec::new();
+
+        // the sponge starts from all zeros
+        for i in 0..STATE_WIDTH {
+            assertions.push(Assertion::single(i, 0, BaseElement::ZERO));
+        }
+
+        // the last block is padded with a ONE followed by zeros
+        if self.pub_inputs.is_padded() {
+            let last_step = self.last_block_step();
+            let num_elements = self.pub_inputs.msg_len % RATE_WIDTH;
+            let padding_column = STATE_WIDTH + num_elements;
+            assertions.push(Assertion::single(padding_column, last_step, BaseElement::ONE));
+            for i in num_elements + 1..RATE_WIDTH {
+                assertions.push(Assertion::single(STATE_WIDTH + i, last_step, BaseElement::ZERO));
+            }
+        }
+
+        // the digest is located in the first two registers once all blocks are absorbed
+        let [h0, h1] = self.pub_inputs.digest.to_elements();
+        assertions.push(Assertion::single(0, self.digest_step, h0));
+        assertions.push(Assertion::single(1, self.digest_step, h1));
+
+        assertions
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        // rounds are applied on all but the first step of a cycle, so the round mask and the
+        // round constants are shifted by one step
+        let mut hash_mask = Rescue6x7::get_round_mask();
+        hash_mask.rotate_right(1);
+
+        let mut padding_flags = vec![BaseElement::ZERO; self.trace_length()];
+        if self.pub_inputs.is_padded() {
+            padding_flags[self.last_block_step()] = BaseElement::ONE;
+        }
+
+        let mut result = vec![hash_mask, padding_flags];
+        for mut column in Rescue6x7::get_round_constants() {
+            column.rotate_right(1);
+            result.push(column);
+        }
+        result
+    }
+}
+
+impl SpongeAir {
+    /// Returns the first step of the cycle in which the last message block is absorbed.
+    fn last_block_step(&self) -> usize {
+        self.digest_step - CYCLE_LENGTH
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of assertions against the padding of the last message block.
+fn num_padding_assertions(pub_inputs: &SpongePublicInputs) -> usize {
+    if pub_inputs.is_padded() {
+        RATE_WIDTH - pub_inputs.msg_len % RATE_WIDTH
+    } else {
+        0
+    }
+}
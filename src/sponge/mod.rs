@@ -0,0 +1,244 @@
+//! Proves knowledge of a message hashing to a public [Rescue128] digest.
+//!
+//! The execution trace consists of [STATE_WIDTH] columns holding the sponge state followed by
+//! [RATE_WIDTH] columns holding the message. Every [CYCLE_LENGTH]-step cycle absorbs one message
+//! block: on the first step of the cycle the block is added to the rate, and the remaining
+//! [NUM_ROUNDS] steps apply the permutation rounds.
+
+use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement, ToElements},
+    Proof, ProofOptions, Prover, TraceTable,
+};
+
+use crate::utils::permutation::AlgebraicPermutation;
+use crate::utils::rescue::{
+    Hash, Rescue128, Rescue6x7, CYCLE_LENGTH, NUM_ROUNDS, RATE_WIDTH, STATE_WIDTH,
+};
+use crate::security::verify_with_policy;
+use crate::{
+    check_blowup_factor, check_trace_length, AirId, Blake3, BundlePublicInputs, Error,
+    SecurityLevel, SecurityPolicy,
+};
+
+mod air;
+pub use air::SpongeAir;
+
+mod prover;
+pub use prover::SpongeProver;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of columns in the execution trace: the sponge state followed by a message block.
+pub const TRACE_WIDTH: usize = STATE_WIDTH + RATE_WIDTH;
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpongePublicInputs {
+    /// Digest of the message, i.e. `Rescue128::digest(msg)`.
+    pub digest: Hash,
+    /// Number of elements in the message; this determines the number of absorbed blocks and
+    /// how the last block is padded.
+    pub msg_len: usize,
+}
+
+impl SpongePublicInputs {
+    /// Returns the number of blocks absorbed by the sponge, including the padded block.
+    pub fn num_blocks(&self) -> usize {
+        num_blocks(self.msg_len)
+    }
+
+    /// Returns true if the last block of the message is padded.
+    pub fn is_padded(&self) -> bool {
+        !self.msg_len.is_multiple_of(RATE_WIDTH) || self.msg_len == 0
+    }
+
+    /// Returns the trace step at which the digest is located, or None if the step does not fit
+    /// into usize.
+    pub fn digest_step(&self) -> Option<usize> {
+        self.num_blocks().checked_mul(CYCLE_LENGTH)
+    }
+}
+
+impl ToElements<BaseElement> for SpongePublicInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = self.digest.to_elements().to_vec();
+        result.push(BaseElement::from(self.msg_len as u64));
+        result
+    }
+}
+
+impl Serializable for SpongePublicInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.digest.write_into(target);
+        target.write_u64(self.msg_len as u64);
+    }
+}
+
+impl Deserializable for SpongePublicInputs {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let digest = Hash::read_from(source)?;
+        let msg_len = source.read_u64()?;
+        let msg_len = usize::try_from(msg_len).map_err(|_| {
+            DeserializationError::InvalidValue(format!(
+                "message length {msg_len} does not fit into usize"
+            ))
+        })?;
+        Ok(SpongePublicInputs { digest, msg_len })
+    }
+}
+
+impl BundlePublicInputs for SpongePublicInputs {
+    const AIR_ID: AirId = AirId::Sponge;
+}
+
+// TRACE BUILDER
+// ================================================================================================
+
+/// Returns the number of blocks absorbed when hashing a message of `msg_len` elements.
+pub fn num_blocks(msg_len: usize) -> usize {
+    if msg_len == 0 {
+        1
+    } else {
+        msg_len.div_ceil(RATE_WIDTH)
+    }
+}
+
+/// Returns the length of the execution trace needed to hash a message of `msg_len` elements, or
+/// None if the length does not fit into usize; one extra cycle is needed to hold the digest
+/// after the last block has been absorbed.
+pub fn trace_length(msg_len: usize) -> Option<usize> {
+    (num_blocks(msg_len) + 1).checked_next_power_of_two()?.checked_mul(CYCLE_LENGTH)
+}
+
+/// Builds an execution trace computing `Rescue128::digest(msg)`.
+///
+/// Message blocks are placed into the message columns on the first step of their cycle, with
+/// the last block padded the same way as [Rescue128::finalize] does. Once all blocks have been
+/// absorbed, the remaining cycles absorb zero blocks.
+///
+/// # Panics
+/// Panics if the length of the trace does not fit into usize.
+pub fn build_trace(msg: &[BaseElement]) -> TraceTable<BaseElement> {
+    let pub_inputs = SpongePublicInputs { digest: Hash::default(), msg_len: msg.len() };
+    let num_blocks = pub_inputs.num_blocks();
+    let trace_length = trace_length(msg.len()).expect("message is too long");
+
+    let mut columns = vec![vec![BaseElement::ZERO; trace_length]; TRACE_WIDTH];
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+    for cycle in 0..trace_length / CYCLE_LENGTH {
+        let first_step = cycle * CYCLE_LENGTH;
+
+        // get the message block absorbed in this cycle
+        let mut block = [BaseElement::ZERO; RATE_WIDTH];
+        if cycle < num_blocks {
+            let start = cycle * RATE_WIDTH;
+            let end = msg.len().min(start + RATE_WIDTH);
+            block[..end - start].copy_from_slice(&msg[start..end]);
+            if cycle == num_blocks - 1 && pub_inputs.is_padded() {
+                block[end - start] = BaseElement::ONE;
+            }
+        }
+
+        for (i, &value) in block.iter().enumerate() {
+            columns[STATE_WIDTH + i][first_step] = value;
+        }
+        write_state(&mut columns, first_step, &state);
+
+        // absorb the block; padding is signaled via the last capacity element
+        for (s, &value) in state.iter_mut().zip(block.iter()) {
+            *s += value;
+        }
+        state[STATE_WIDTH - 1] += get_padding_flag(&pub_inputs, cycle);
+        write_state(&mut columns, first_step + 1, &state);
+
+        // apply the permutation rounds
+        for round in 0..NUM_ROUNDS {
+            Rescue6x7::apply_round(&mut state, round);
+            if first_step + round + 2 < trace_length {
+                write_state(&mut columns, first_step + round + 2, &state);
+            }
+        }
+    }
+
+    // the trace holds the digest step, so computing the step cannot overflow
+    let digest_step = num_blocks * CYCLE_LENGTH;
+    debug_assert_eq!(
+        Rescue128::digest(msg).to_elements(),
+        [columns[0][digest_step], columns[1][digest_step]],
+        "trace does not compute the digest of the message"
+    );
+
+    TraceTable::init(columns)
+}
+
+/// Returns ONE if the block absorbed in the specified cycle is the padded last block.
+fn get_padding_flag(pub_inputs: &SpongePublicInputs, cycle: usize) -> BaseElement {
+    if pub_inputs.is_padded() && cycle == pub_inputs.num_blocks() - 1 {
+        BaseElement::ONE
+    } else {
+        BaseElement::ZERO
+    }
+}
+
+fn write_state(columns: &mut [Vec<BaseElement>], step: usize, state: &[BaseElement]) {
+    for (column, &value) in columns.iter_mut().zip(state.iter()) {
+        column[step] = value;
+    }
+}
+
+// PROVE AND VERIFY
+// ================================================================================================
+
+/// Generates a STARK proof attesting that the prover knows a message hashing to
+/// `Rescue128::digest(msg)`; only the digest and the length of the message are public.
+pub fn prove_sponge(
+    msg: &[BaseElement],
+    options: ProofOptions,
+) -> Result<(SpongePublicInputs, Proof), Error> {
+    check_trace_length(trace_length(msg.len()), &options)?;
+    check_blowup_factor(&options, Rescue6x7::CONSTRAINT_DEGREE)?;
+
+    let trace = build_trace(msg);
+    let prover = SpongeProver::new(options, msg.len());
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace)?;
+    Ok((pub_inputs, proof))
+}
+
+/// Verifies that `proof` attests to the knowledge of a message described by `pub_inputs`, and
+/// returns the security level of the proof.
+pub fn verify_sponge(
+    pub_inputs: SpongePublicInputs,
+    proof: Proof,
+) -> Result<SecurityLevel, Error> {
+    verify_sponge_with(pub_inputs, proof, &SecurityPolicy::default())
+}
+
+/// Same as [verify_sponge], but accepts the proof under the specified security policy.
+pub fn verify_sponge_with(
+    pub_inputs: SpongePublicInputs,
+    proof: Proof,
+    policy: &SecurityPolicy,
+) -> Result<SecurityLevel, Error> {
+    let trace_info = proof.trace_info();
+    if trace_info.width() != TRACE_WIDTH {
+        return Err(Error::PublicInputMismatch(format!(
+            "expected trace width {TRACE_WIDTH}, but proof trace width is {}",
+            trace_info.width()
+        )));
+    }
+    if pub_inputs.digest_step().is_none_or(|step| step >= trace_info.length()) {
+        return Err(Error::PublicInputMismatch(format!(
+            "trace of length {} cannot hold a message of {} elements",
+            trace_info.length(),
+            pub_inputs.msg_len
+        )));
+    }
+    check_blowup_factor(proof.options(), Rescue6x7::CONSTRAINT_DEGREE)?;
+
+    verify_with_policy::<SpongeAir, Blake3>(proof, pub_inputs, policy)
+}
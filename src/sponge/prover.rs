@@ -0,0 +1,66 @@
+use winter_air::PartitionOptions;
+use winterfell::{
+    crypto::{DefaultRandomCoin, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    matrix::ColMatrix,
+    AuxRandElements, DefaultConstraintEvaluator, DefaultTraceLde, ProofOptions, Prover,
+    StarkDomain, TraceInfo, TracePolyTable, TraceTable,
+};
+
+use super::{SpongeAir, SpongePublicInputs};
+use crate::utils::rescue::Hash;
+use crate::Blake3;
+
+pub struct SpongeProver {
+    options: ProofOptions,
+    msg_len: usize,
+}
+
+impl SpongeProver {
+    /// Returns a prover for the digest of a message with `msg_len` elements.
+    pub fn new(options: ProofOptions, msg_len: usize) -> Self {
+        Self { options, msg_len }
+    }
+}
+
+impl Prover for SpongeProver {
+    type BaseField = BaseElement;
+    type Air = SpongeAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+    type VC = MerkleTree<Self::HashFn>;
+    type RandomCoin = DefaultRandomCoin<Blake3>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Blake3, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> =
+        DefaultConstraintEvaluator<'a, SpongeAir, E>;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> SpongePublicInputs {
+        let mut pub_inputs = SpongePublicInputs { digest: Hash::default(), msg_len: self.msg_len };
+        let digest_step = pub_inputs.digest_step().expect("message is too long");
+        pub_inputs.digest = Hash::new(trace.get(0, digest_step), trace.get(1, digest_step));
+        pub_inputs
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<Self::BaseField>,
+        domain: &StarkDomain<Self::BaseField>,
+        partition_option: PartitionOptions,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        DefaultTraceLde::new(trace_info, main_trace, domain, partition_option)
+    }
+
+    fn new_evaluator<'a, E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        air: &'a SpongeAir,
+        aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: winterfell::ConstraintCompositionCoefficients<E>,
+    ) -> Self::ConstraintEvaluator<'a, E> {
+        DefaultConstraintEvaluator::new(air, aux_rand_elements, composition_coefficients)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
@@ -10,6 +10,7 @@ use winterfell::{
     Trace, TraceTable,
 };
 
+pub mod permutation;
 pub mod rescue;
 
 // CONSTRAINT EVALUATION HELPERS
@@ -73,14 +74,7 @@ pub fn print_trace<E: StarkField>(
             continue;
         }
         trace.read_row_into(i, &mut state);
-        println!(
-            "{}\t{:?}",
-            i,
-            state[range.clone()]
-                .iter()
-                .map(|v| v.as_int())
-                .collect::<Vec<E::PositiveInteger>>()
-        );
+        println!("{}", format_trace_row(i, &state[range.clone()]));
     }
 }
 
@@ -90,9 +84,10 @@ pub fn print_trace_step<E: StarkField>(trace: &[Vec<E>], step: usize) {
     for i in 0..trace_width {
         state[i] = trace[i][step];
     }
-    println!(
-        "{}\t{:?}",
-        step,
-        state.iter().map(|v| v.as_int()).collect::<Vec<E::PositiveInteger>>()
-    );
+    println!("{}", format_trace_row(step, &state));
+}
+
+/// Formats a row of an execution trace the way [print_trace] prints it.
+pub fn format_trace_row<E: StarkField>(step: usize, row: &[E]) -> String {
+    format!("{}\t{:?}", step, row.iter().map(|v| v.as_int()).collect::<Vec<E::PositiveInteger>>())
 }
@@ -0,0 +1,175 @@
+use winterfell::math::{fields::f128::BaseElement, FieldElement};
+
+use crate::utils::{are_equal, EvaluationResult};
+
+// ALGEBRAIC PERMUTATION
+// ================================================================================================
+
+/// An algebraic permutation which can be computed natively and described by AIR constraints.
+///
+/// The permutation consists of [Self::NUM_ROUNDS] rounds applied over a state of
+/// [Self::STATE_WIDTH] field elements. In an execution trace, one permutation occupies a cycle of
+/// [Self::CYCLE_LENGTH] steps: a round is applied on each of the first [Self::NUM_ROUNDS] steps,
+/// and the remaining steps are left for the AIR to use.
+pub trait AlgebraicPermutation: Send + Sync + 'static {
+    /// Number of field elements in the permutation state.
+    const STATE_WIDTH: usize;
+
+    /// Number of rounds in a single application of the permutation.
+    const NUM_ROUNDS: usize;
+
+    /// Number of trace steps needed to describe the permutation; must be a power of two greater
+    /// than [Self::NUM_ROUNDS].
+    const CYCLE_LENGTH: usize;
+
+    /// Degree of the constraints enforced by [Self::enforce_round].
+    const CONSTRAINT_DEGREE: usize;
+
+    /// Applies a single round of the permutation to the provided state; the round constants are
+    /// selected based on `step % CYCLE_LENGTH`.
+    fn apply_round(state: &mut [BaseElement], step: usize);
+
+    /// When flag = 1, enforces constraints for a single round of the permutation. `ark` contains
+    /// the round constants for the current step as returned by [Self::get_round_constants].
+    fn enforce_round<E: FieldElement + From<BaseElement>>(
+        result: &mut [E],
+        current: &[E],
+        next: &[E],
+        ark: &[E],
+        flag: E,
+    );
+
+    /// Returns round constants arranged in column-major form, i.e. as periodic columns of
+    /// length [Self::CYCLE_LENGTH].
+    fn get_round_constants() -> Vec<Vec<BaseElement>>;
+
+    /// Applies the full permutation to the provided state.
+    fn apply_permutation(state: &mut [BaseElement]) {
+        for i in 0..Self::NUM_ROUNDS {
+            Self::apply_round(state, i);
+        }
+    }
+
+    /// Returns a periodic column which is ONE on the steps where a round is applied, and ZERO on
+    /// the remaining steps of the cycle.
+    fn get_round_mask() -> Vec<BaseElement> {
+        let mut mask = vec![BaseElement::ZERO; Self::CYCLE_LENGTH];
+        mask[..Self::NUM_ROUNDS].fill(BaseElement::ONE);
+        mask
+    }
+}
+
+// RESCUE ROUND FUNCTION
+// ================================================================================================
+// Building blocks shared by all Rescue parameter sets; `W` is the width of the state.
+
+/// Rescue round function parametrized by the S-Box power, its inverse and the MDS matrix;
+/// implementation based on algorithm 3 from <https://eprint.iacr.org/2020/1143.pdf>
+#[inline(always)]
+pub fn apply_rescue_round<const W: usize>(
+    state: &mut [BaseElement],
+    ark: &[BaseElement],
+    alpha: u32,
+    inv_alpha: u128,
+    mds: &[BaseElement],
+) {
+    // apply first half of Rescue round
+    apply_sbox::<_, W>(state, alpha);
+    apply_mds::<_, W>(state, mds);
+    for i in 0..W {
+        state[i] += ark[i];
+    }
+
+    // apply second half of Rescue round
+    apply_inv_sbox::<W>(state, inv_alpha);
+    apply_mds::<_, W>(state, mds);
+    for i in 0..W {
+        state[i] += ark[W + i];
+    }
+}
+
+/// when flag = 1, enforces constraints for a single round of Rescue hash functions
+#[allow(clippy::too_many_arguments)]
+pub fn enforce_rescue_round<E: FieldElement + From<BaseElement>, const W: usize>(
+    result: &mut [E],
+    current: &[E],
+    next: &[E],
+    ark: &[E],
+    flag: E,
+    alpha: u32,
+    mds: &[BaseElement],
+    inv_mds: &[BaseElement],
+) {
+    // compute the state that should result from applying the first half of Rescue round
+    // to the current state of the computation
+    let mut step1 = [E::ZERO; W];
+    step1.copy_from_slice(&current[..W]);
+    apply_sbox::<_, W>(&mut step1, alpha);
+    apply_mds::<_, W>(&mut step1, mds);
+    for i in 0..W {
+        step1[i] += ark[i];
+    }
+
+    // compute the state that should result from applying the inverse for the second
+    // half for Rescue round to the next step of the computation
+    let mut step2 = [E::ZERO; W];
+    step2.copy_from_slice(&next[..W]);
+    for i in 0..W {
+        step2[i] -= ark[W + i];
+    }
+    apply_mds::<_, W>(&mut step2, inv_mds);
+    apply_sbox::<_, W>(&mut step2, alpha);
+
+    // make sure that the results are equal
+    for i in 0..W {
+        result.agg_constraint(i, flag, are_equal(step2[i], step1[i]));
+    }
+}
+
+/// Transposes Rescue round constants from one row per step into one column per constant.
+pub fn get_rescue_round_constants<const N: usize>(
+    ark: &[[BaseElement; N]],
+) -> Vec<Vec<BaseElement>> {
+    (0..N).map(|j| ark.iter().map(|row| row[j]).collect()).collect()
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+#[allow(clippy::needless_range_loop)]
+fn apply_sbox<E: FieldElement, const W: usize>(state: &mut [E], alpha: u32) {
+    for i in 0..W {
+        state[i] = state[i].exp(alpha.into());
+    }
+}
+
+#[inline(always)]
+#[allow(clippy::needless_range_loop)]
+fn apply_inv_sbox<const W: usize>(state: &mut [BaseElement], inv_alpha: u128) {
+    for i in 0..W {
+        state[i] = state[i].exp(inv_alpha);
+    }
+}
+
+/// Multiplies the state by the provided matrix, which is given in row-major form; the same
+/// function is used for both the MDS matrix and its inverse.
+#[inline(always)]
+#[allow(clippy::needless_range_loop)]
+fn apply_mds<E: FieldElement + From<BaseElement>, const W: usize>(
+    state: &mut [E],
+    mds: &[BaseElement],
+) {
+    let mut result = [E::ZERO; W];
+    let mut temp = [E::ZERO; W];
+    for i in 0..W {
+        for j in 0..W {
+            temp[j] = E::from(mds[i * W + j]) * state[j];
+        }
+
+        for j in 0..W {
+            result[i] += temp[j];
+        }
+    }
+    state[..W].copy_from_slice(&result);
+}
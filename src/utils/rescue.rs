@@ -7,11 +7,13 @@ use core::slice;
 
 use winter_utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 use winterfell::{
-    crypto::{Digest, Hasher},
+    crypto::{Digest, ElementHasher, Hasher},
     math::{fields::f128::BaseElement, FieldElement},
 };
 
-use crate::utils::{are_equal, EvaluationResult};
+use crate::utils::permutation::{
+    apply_rescue_round, enforce_rescue_round, get_rescue_round_constants, AlgebraicPermutation,
+};
 
 /// Function state is set to 6 field elements or 96 bytes; 4 elements are reserved for rate
 /// and 2 elements are reserved for capacity.
@@ -21,6 +23,10 @@ pub const RATE_WIDTH: usize = 4;
 /// Two elements (32-bytes) are returned as digest.
 const DIGEST_SIZE: usize = 2;
 
+/// Number of bytes packed into a single field element when hashing a byte string; 15 bytes
+/// leave enough headroom below the 128-bit field modulus for the padding byte.
+const BYTES_PER_ELEMENT: usize = 15;
+
 /// Number of rounds in a single permutation of the hash function.
 ///
 /// The number of rounds is set to 7 to provide 128-bit security level with 40% security margin;
@@ -57,47 +63,41 @@ impl Rescue128 {
     }
 
     /// Absorbs data into the hasher state.
+    ///
+    /// The permutation is applied lazily, right before the next element is absorbed into a full
+    /// rate, so that [Self::finalize] can tell whether the last block needs padding.
     pub fn update(&mut self, data: &[BaseElement]) {
         for &element in data {
-            self.state[self.idx] += element;
-            self.idx += 1;
-            if self.idx % RATE_WIDTH == 0 {
+            if self.idx == RATE_WIDTH {
                 apply_permutation(&mut self.state);
                 self.idx = 0;
             }
+            self.state[self.idx] += element;
+            self.idx += 1;
         }
     }
 
     /// Returns hash of the data absorbed into the hasher.
+    ///
+    /// If the last block is not full (this includes empty input), it is padded with a single
+    /// ONE followed by zeros, and ONE is added to the last capacity element to separate padded
+    /// inputs from unpadded ones. This makes the padding injective: no two distinct inputs are
+    /// absorbed as the same sequence of blocks.
     pub fn finalize(mut self) -> Hash {
-        if self.idx > 0 {
-            // TODO: apply proper padding
-            apply_permutation(&mut self.state);
+        if self.idx < RATE_WIDTH {
+            self.state[self.idx] += BaseElement::ONE;
+            self.state[STATE_WIDTH - 1] += BaseElement::ONE;
         }
+        apply_permutation(&mut self.state);
         Hash([self.state[0], self.state[1]])
     }
 
-    /// Returns hash of the provided data.
+    /// Returns hash of the provided data; this is equivalent to absorbing the data with
+    /// [Self::update] and calling [Self::finalize].
     pub fn digest(data: &[BaseElement]) -> Hash {
-        // initialize state to all zeros
-        let mut state = [BaseElement::ZERO; STATE_WIDTH];
-
-        let mut i = 0;
-        for &element in data.iter() {
-            state[i] += element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                apply_permutation(&mut state);
-                i = 0;
-            }
-        }
-
-        if i > 0 {
-            // TODO: apply proper padding
-            apply_permutation(&mut state);
-        }
-
-        Hash([state[0], state[1]])
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
     }
 }
 
@@ -109,23 +109,59 @@ impl Hasher for Rescue128 {
 
     const COLLISION_RESISTANCE: u32 = 64;
 
-    fn hash(_bytes: &[u8]) -> Self::Digest {
-        unimplemented!("not implemented")
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        Self::digest(&bytes_to_elements(bytes))
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
         Self::digest(Hash::hashes_as_elements(values))
     }
 
-    fn merge_many(_values: &[Self::Digest]) -> Self::Digest {
-        unimplemented!("not implemented")
+    fn merge_many(values: &[Self::Digest]) -> Self::Digest {
+        Self::digest(Hash::hashes_as_elements(values))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let [s0, s1] = seed.to_elements();
+        Self::digest(&[s0, s1, BaseElement::from(value)])
     }
+}
 
-    fn merge_with_int(_seed: Self::Digest, _value: u64) -> Self::Digest {
-        unimplemented!("not implemented")
+impl ElementHasher for Rescue128 {
+    type BaseField = BaseElement;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        Self::digest(E::slice_as_base_elements(elements))
     }
 }
 
+/// Packs a byte string into field elements.
+///
+/// Bytes are split into chunks of [BYTES_PER_ELEMENT] bytes, each read as a little-endian
+/// integer. A single `1` byte is appended right after the last byte of the input so that
+/// strings which differ only in trailing zero bytes map to different elements; an empty string
+/// therefore maps to a single element equal to one.
+fn bytes_to_elements(bytes: &[u8]) -> Vec<BaseElement> {
+    let num_elements = bytes.len() / BYTES_PER_ELEMENT + 1;
+    let mut elements = Vec::with_capacity(num_elements);
+
+    let mut chunks = bytes.chunks_exact(BYTES_PER_ELEMENT);
+    for chunk in chunks.by_ref() {
+        let mut buf = [0u8; 16];
+        buf[..BYTES_PER_ELEMENT].copy_from_slice(chunk);
+        elements.push(BaseElement::new(u128::from_le_bytes(buf)));
+    }
+
+    // the last chunk always has room for the padding byte
+    let remainder = chunks.remainder();
+    let mut buf = [0u8; 16];
+    buf[..remainder.len()].copy_from_slice(remainder);
+    buf[remainder.len()] = 1;
+    elements.push(BaseElement::new(u128::from_le_bytes(buf)));
+
+    elements
+}
+
 // HASH IMPLEMENTATION
 // ================================================================================================
 
@@ -182,144 +218,47 @@ impl Deserializable for Hash {
 // RESCUE PERMUTATION
 // ================================================================================================
 
-/// Applies Rescue-XLIX permutation to the provided state.
-pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
-    // apply round function 7 times; this provides 128-bit security with 40% security margin
-    for i in 0..NUM_ROUNDS {
-        apply_round(state, i);
+/// Rescue-XLIX permutation with a 6 element state, 7 rounds and S-Box power 5; this is the
+/// permutation underlying [Rescue128].
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Rescue6x7;
+
+impl AlgebraicPermutation for Rescue6x7 {
+    const STATE_WIDTH: usize = STATE_WIDTH;
+    const NUM_ROUNDS: usize = NUM_ROUNDS;
+    const CYCLE_LENGTH: usize = CYCLE_LENGTH;
+    const CONSTRAINT_DEGREE: usize = ALPHA as usize;
+
+    /// Rescue-XLIX round function;
+    /// implementation based on algorithm 3 from <https://eprint.iacr.org/2020/1143.pdf>
+    #[inline(always)]
+    fn apply_round(state: &mut [BaseElement], step: usize) {
+        // determine which round constants to use
+        let ark = &ARK[step % CYCLE_LENGTH];
+        apply_rescue_round::<STATE_WIDTH>(state, ark, ALPHA, INV_ALPHA, &MDS);
     }
-}
 
-/// Rescue-XLIX round function;
-/// implementation based on algorithm 3 from <https://eprint.iacr.org/2020/1143.pdf>
-#[inline(always)]
-pub fn apply_round(state: &mut [BaseElement], step: usize) {
-    // determine which round constants to use
-    let ark = ARK[step % CYCLE_LENGTH];
-
-    // apply first half of Rescue round
-    apply_sbox(state);
-    apply_mds(state);
-    for i in 0..STATE_WIDTH {
-        state[i] += ark[i];
+    fn enforce_round<E: FieldElement + From<BaseElement>>(
+        result: &mut [E],
+        current: &[E],
+        next: &[E],
+        ark: &[E],
+        flag: E,
+    ) {
+        enforce_rescue_round::<E, STATE_WIDTH>(
+            result, current, next, ark, flag, ALPHA, &MDS, &INV_MDS,
+        );
     }
 
-    // apply second half of Rescue round
-    apply_inv_sbox(state);
-    apply_mds(state);
-    for i in 0..STATE_WIDTH {
-        state[i] += ark[STATE_WIDTH + i];
+    fn get_round_constants() -> Vec<Vec<BaseElement>> {
+        get_rescue_round_constants(&ARK)
     }
 }
 
-// CONSTRAINTS
-// ================================================================================================
-
-/// when flag = 1, enforces constraints for a single round of Rescue hash functions
-pub fn enforce_round<E: FieldElement + From<BaseElement>>(
-    result: &mut [E],
-    current: &[E],
-    next: &[E],
-    ark: &[E],
-    flag: E,
-) {
-    // compute the state that should result from applying the first half of Rescue round
-    // to the current state of the computation
-    let mut step1 = [E::ZERO; STATE_WIDTH];
-    step1.copy_from_slice(current);
-    apply_sbox(&mut step1);
-    apply_mds(&mut step1);
-    for i in 0..STATE_WIDTH {
-        step1[i] += ark[i];
-    }
-
-    // compute the state that should result from applying the inverse for the second
-    // half for Rescue round to the next step of the computation
-    let mut step2 = [E::ZERO; STATE_WIDTH];
-    step2.copy_from_slice(next);
-    for i in 0..STATE_WIDTH {
-        step2[i] -= ark[STATE_WIDTH + i];
-    }
-    apply_inv_mds(&mut step2);
-    apply_sbox(&mut step2);
-
-    // make sure that the results are equal
-    for i in 0..STATE_WIDTH {
-        result.agg_constraint(i, flag, are_equal(step2[i], step1[i]));
-    }
-}
-
-// ROUND CONSTANTS
-// ================================================================================================
-
-/// Returns Rescue round constants arranged in column-major form.
-pub fn get_round_constants() -> Vec<Vec<BaseElement>> {
-    let mut constants = Vec::new();
-    for _ in 0..(STATE_WIDTH * 2) {
-        constants.push(vec![BaseElement::ZERO; CYCLE_LENGTH]);
-    }
-
-    #[allow(clippy::needless_range_loop)]
-    for i in 0..CYCLE_LENGTH {
-        for j in 0..(STATE_WIDTH * 2) {
-            constants[j][i] = ARK[i][j];
-        }
-    }
-
-    constants
-}
-
-// HELPER FUNCTIONS
-// ================================================================================================
-
-#[inline(always)]
-#[allow(clippy::needless_range_loop)]
-fn apply_sbox<E: FieldElement>(state: &mut [E]) {
-    for i in 0..STATE_WIDTH {
-        state[i] = state[i].exp(ALPHA.into());
-    }
-}
-
-#[inline(always)]
-#[allow(clippy::needless_range_loop)]
-fn apply_inv_sbox(state: &mut [BaseElement]) {
-    for i in 0..STATE_WIDTH {
-        state[i] = state[i].exp(INV_ALPHA);
-    }
-}
-
-#[inline(always)]
-#[allow(clippy::needless_range_loop)]
-fn apply_mds<E: FieldElement + From<BaseElement>>(state: &mut [E]) {
-    let mut result = [E::ZERO; STATE_WIDTH];
-    let mut temp = [E::ZERO; STATE_WIDTH];
-    for i in 0..STATE_WIDTH {
-        for j in 0..STATE_WIDTH {
-            temp[j] = E::from(MDS[i * STATE_WIDTH + j]) * state[j];
-        }
-
-        for j in 0..STATE_WIDTH {
-            result[i] += temp[j];
-        }
-    }
-    state.copy_from_slice(&result);
-}
-
-#[inline(always)]
-#[allow(clippy::needless_range_loop)]
-fn apply_inv_mds<E: FieldElement + From<BaseElement>>(state: &mut [E]) {
-    let mut result = [E::ZERO; STATE_WIDTH];
-    let mut temp = [E::ZERO; STATE_WIDTH];
-    for i in 0..STATE_WIDTH {
-        for j in 0..STATE_WIDTH {
-            temp[j] = E::from(INV_MDS[i * STATE_WIDTH + j]) * state[j];
-        }
-
-        for j in 0..STATE_WIDTH {
-            result[i] += temp[j];
-        }
-    }
-    state.copy_from_slice(&result);
+/// Applies Rescue-XLIX permutation to the provided state.
+pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+    // apply round function 7 times; this provides 128-bit security with 40% security margin
+    Rescue6x7::apply_permutation(state);
 }
 
 // CONSTANTS
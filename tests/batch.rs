@@ -0,0 +1,120 @@
+use stark_test::batch::{
+    batch_layout, max_batch_width, prove_batch, verify_batch_with, BatchPublicInputs,
+};
+use stark_test::{hash_chain, Error, Rescue4x14};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    FieldExtension, ProofOptions, VerifierError,
+};
+
+mod common;
+use common::{options, policy};
+
+fn seeds(count: u128) -> Vec<[BaseElement; 2]> {
+    (0..count).map(|i| [BaseElement::new(2 * i), BaseElement::new(2 * i + 1)]).collect()
+}
+
+#[test]
+fn proofs_of_batches_verify() {
+    let seeds = seeds(3);
+    let (pub_inputs, proof) = prove_batch(&seeds, 4, options()).unwrap();
+    assert_eq!(seeds, pub_inputs.seeds());
+    for (seed, result) in seeds.iter().zip(pub_inputs.results()) {
+        assert_eq!(hash_chain(*seed, 4), *result);
+    }
+    assert!(verify_batch_with(pub_inputs, proof, &policy()).is_ok());
+}
+
+#[test]
+fn tampered_result_is_rejected() {
+    let (pub_inputs, proof) = prove_batch(&seeds(3), 4, options()).unwrap();
+    let with_results = |results: Vec<[BaseElement; 2]>| {
+        BatchPublicInputs::new(pub_inputs.seeds().to_vec(), results, 4).unwrap()
+    };
+    for chain in 0..3 {
+        let mut results = pub_inputs.results().to_vec();
+        results[chain][0] += BaseElement::ONE;
+        let err = verify_batch_with(with_results(results), proof.clone(), &policy()).unwrap_err();
+        assert!(
+            matches!(err, Error::Verifier(VerifierError::InconsistentOodConstraintEvaluations)),
+            "unexpected error for chain {chain}: {err:?}"
+        );
+    }
+
+    // results of two chains swapped
+    let mut results = pub_inputs.results().to_vec();
+    results.swap(0, 1);
+    assert!(verify_batch_with(with_results(results), proof, &policy()).is_err());
+}
+
+#[test]
+fn mismatched_number_of_results_is_rejected() {
+    for (seeds, results) in [(seeds(3), seeds(2)), (seeds(2), seeds(3)), (vec![], vec![])] {
+        let err = BatchPublicInputs::new(seeds, results, 4).unwrap_err();
+        assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+    }
+}
+
+#[test]
+fn large_batches_are_laid_out_in_segments() {
+    assert_eq!(63, max_batch_width::<Rescue4x14>());
+    assert_eq!((3, 1), batch_layout::<Rescue4x14>(3));
+    assert_eq!((63, 1), batch_layout::<Rescue4x14>(63));
+    assert_eq!((32, 2), batch_layout::<Rescue4x14>(64));
+    assert_eq!((33, 4), batch_layout::<Rescue4x14>(130));
+    assert_eq!((63, 64), batch_layout::<Rescue4x14>(4000));
+}
+
+#[test]
+fn thousands_of_chains_verify_in_one_proof() {
+    let seeds = seeds(1000);
+    let (pub_inputs, proof) = prove_batch(&seeds, 1, options()).unwrap();
+    assert_eq!(seeds, pub_inputs.seeds());
+    for (seed, result) in seeds.iter().zip(pub_inputs.results()) {
+        assert_eq!(hash_chain(*seed, 1), *result);
+    }
+    assert!(verify_batch_with(pub_inputs.clone(), proof.clone(), &policy()).is_ok());
+
+    // a result in a later segment, and the last chain, which also fills the unused segments
+    for chain in [17, 999] {
+        let mut results = pub_inputs.results().to_vec();
+        results[chain][1] += BaseElement::ONE;
+        let pub_inputs = BatchPublicInputs::new(seeds.clone(), results, 1).unwrap();
+        let err = verify_batch_with(pub_inputs, proof.clone(), &policy()).unwrap_err();
+        assert!(
+            matches!(err, Error::Verifier(VerifierError::InconsistentOodConstraintEvaluations)),
+            "unexpected error for chain {chain}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn partially_filled_segments_verify() {
+    // 130 chains take 33 column groups of 4 segments; the last group repeats the last chain
+    let seeds = seeds(130);
+    let (pub_inputs, proof) = prove_batch(&seeds, 2, options()).unwrap();
+    assert_eq!(32 * 4, proof.trace_info().length());
+    assert_eq!(33 * 4, proof.trace_info().width());
+    assert_eq!(hash_chain(seeds[129], 2), pub_inputs.results()[129]);
+    assert!(verify_batch_with(pub_inputs.clone(), proof.clone(), &policy()).is_ok());
+
+    // the same proof does not attest to a batch without the last chain
+    let pub_inputs = BatchPublicInputs::new(
+        seeds[..129].to_vec(),
+        pub_inputs.results()[..129].to_vec(),
+        2,
+    )
+    .unwrap();
+    assert!(verify_batch_with(pub_inputs, proof, &policy()).is_err());
+}
+
+#[test]
+fn invalid_options_are_rejected() {
+    // constraints of degree 3 need a blowup factor of at least 4
+    let small_blowup = ProofOptions::new(28, 2, 0, FieldExtension::None, 8, 31);
+    let err = prove_batch(&seeds(2), 3, small_blowup).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+
+    let err = prove_batch(&seeds(2), 1 << 62, options()).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+}
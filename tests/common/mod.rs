@@ -0,0 +1,19 @@
+//! Fixtures shared by the integration tests; every test crate uses only some of them.
+#![allow(dead_code)]
+
+use stark_test::{Error, SecurityPolicy};
+use winterfell::{FieldExtension, ProofOptions, VerifierError};
+
+pub fn options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 8, 31)
+}
+
+/// Proofs generated by the tests are weaker than the default policy requires.
+pub fn policy() -> SecurityPolicy {
+    SecurityPolicy::MinConjectured(0)
+}
+
+/// Returns true if verification failed because the proof attests to different public inputs.
+pub fn is_inconsistent(result: Result<impl Sized, Error>) -> bool {
+    matches!(result, Err(Error::Verifier(VerifierError::InconsistentOodConstraintEvaluations)))
+}
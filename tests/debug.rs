@@ -0,0 +1,124 @@
+use stark_test::debug::{check_constraints, ConstraintError};
+use stark_test::merkle::{self, MerkleAir, MerklePublicInputs};
+use stark_test::utils::format_trace_row;
+use stark_test::utils::rescue::Hash;
+use stark_test::{build_trace, result_step, PublicInputs, Rescue4x14, TrainAir};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, FieldExtension, ProofOptions, Trace, TraceTable,
+};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+const ITERATIONS: usize = 5;
+
+fn options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 8, 31)
+}
+
+fn do_work_air(trace: &TraceTable<BaseElement>) -> TrainAir {
+    let step = result_step::<Rescue4x14>(ITERATIONS);
+    let result = [trace.get(0, step), trace.get(1, step)];
+    let pub_inputs = PublicInputs { seed: SEED, result, iterations: ITERATIONS };
+    TrainAir::new(trace.info().clone(), pub_inputs, options())
+}
+
+fn row(trace: &TraceTable<BaseElement>, step: usize) -> Vec<BaseElement> {
+    let mut row = vec![BaseElement::ZERO; trace.width()];
+    trace.read_row_into(step, &mut row);
+    row
+}
+
+#[test]
+fn valid_traces_satisfy_constraints() {
+    let trace = build_trace::<Rescue4x14>(SEED, ITERATIONS);
+    check_constraints(&do_work_air(&trace), &trace).unwrap();
+
+    let leaf = Hash::new(BaseElement::new(1), BaseElement::new(2));
+    let path: Vec<Hash> =
+        (3..9).map(|i| Hash::new(BaseElement::new(i), BaseElement::new(i + 1))).collect();
+    let trace = merkle::build_trace(leaf, 37, &path, 4, &mut rand::thread_rng());
+    let pub_inputs = MerklePublicInputs { root: merkle::compute_root(leaf, 37, &path), depth: 6 };
+    let air = MerkleAir::new(trace.info().clone(), pub_inputs, options());
+    check_constraints(&air, &trace).unwrap();
+}
+
+#[test]
+fn tampered_round_reports_first_failing_transition() {
+    let mut trace = build_trace::<Rescue4x14>(SEED, ITERATIONS);
+    let air = do_work_air(&trace);
+    trace.set(2, 21, trace.get(2, 21) + BaseElement::ONE);
+
+    let err = check_constraints(&air, &trace).unwrap_err();
+    let ConstraintError::Transition { step, current, next, .. } = &err else {
+        panic!("expected a transition constraint failure, but got: {err}");
+    };
+    assert_eq!(20, *step);
+    assert_eq!(&row(&trace, 20), current);
+    assert_eq!(&row(&trace, 21), next);
+
+    // the report shows both rows the way print_trace does
+    let lines: Vec<String> = err.to_string().lines().map(String::from).collect();
+    assert_eq!(3, lines.len());
+    assert_eq!(format_trace_row(20, current), lines[1]);
+    assert_eq!(format_trace_row(21, next), lines[2]);
+}
+
+#[test]
+fn tampered_copy_step_reports_transition() {
+    let mut trace = build_trace::<Rescue4x14>(SEED, ITERATIONS);
+    let air = do_work_air(&trace);
+    // step 15 is the last step of the first cycle, where registers 2 and 3 must be reset to zero
+    trace.set(3, 16, BaseElement::new(7));
+
+    let err = check_constraints(&air, &trace).unwrap_err();
+    assert!(
+        matches!(err, ConstraintError::Transition { step: 15, constraint: 3, .. }),
+        "unexpected failure: {err}"
+    );
+}
+
+#[test]
+fn wrong_result_reports_assertion() {
+    let trace = build_trace::<Rescue4x14>(SEED, ITERATIONS);
+    let step = result_step::<Rescue4x14>(ITERATIONS);
+    let actual = trace.get(1, step);
+    let result = [trace.get(0, step), actual + BaseElement::ONE];
+    let pub_inputs = PublicInputs { seed: SEED, result, iterations: ITERATIONS };
+    let air: TrainAir = TrainAir::new(trace.info().clone(), pub_inputs, options());
+
+    let err = check_constraints(&air, &trace).unwrap_err();
+    let expected = ConstraintError::Assertion {
+        step,
+        column: 1,
+        expected: result[1],
+        actual,
+        row: row(&trace, step),
+    };
+    assert_eq!(expected, err);
+    let report = err.to_string();
+    assert_eq!(Some(format_trace_row(step, &row(&trace, step)).as_str()), report.lines().nth(1));
+}
+
+#[test]
+fn tampered_seed_reports_assertion_before_transition() {
+    let mut trace = build_trace::<Rescue4x14>(SEED, ITERATIONS);
+    let air = do_work_air(&trace);
+    trace.set(0, 0, BaseElement::new(41));
+
+    let err = check_constraints(&air, &trace).unwrap_err();
+    assert!(
+        matches!(err, ConstraintError::Assertion { step: 0, column: 0, .. }),
+        "unexpected failure: {err}"
+    );
+}
+
+#[test]
+fn trace_of_wrong_shape_is_rejected() {
+    let trace = build_trace::<Rescue4x14>(SEED, ITERATIONS);
+    let air = do_work_air(&trace);
+    let longer = build_trace::<Rescue4x14>(SEED, 2 * ITERATIONS);
+
+    let err = check_constraints(&air, &longer).unwrap_err();
+    let expected = ConstraintError::TraceShapeMismatch { expected: (4, 128), actual: (4, 256) };
+    assert_eq!(expected, err);
+}
@@ -0,0 +1,104 @@
+use stark_test::batch::{prove_batch, verify_batch};
+use stark_test::hiding::{prove_hidden_seed, verify_hidden_seed};
+use stark_test::merkle::{prove_membership, verify_membership};
+use stark_test::sponge::{prove_sponge, verify_sponge};
+use stark_test::utils::rescue::Rescue128;
+use stark_test::{
+    options_for_security, production_options, prove_do_work, trace_length, verify_do_work, Blake3,
+    Error, Rescue4x14, PRODUCTION_SECURITY_LEVEL,
+};
+use winterfell::{
+    crypto::Hasher, math::fields::f128::BaseElement, FieldExtension, ProofOptions, ProverError,
+};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+fn options(extension: FieldExtension) -> ProofOptions {
+    ProofOptions::new(32, 8, 0, extension, 8, 31)
+}
+
+// DO WORK
+// ================================================================================================
+
+#[test]
+fn do_work_without_extension() {
+    let (pub_inputs, proof) = prove_do_work(SEED, 8, options(FieldExtension::None)).unwrap();
+    assert_eq!(FieldExtension::None, proof.options().field_extension());
+    verify_do_work(pub_inputs, proof).unwrap();
+}
+
+#[test]
+fn do_work_with_quadratic_extension() {
+    let (pub_inputs, proof) = prove_do_work(SEED, 8, options(FieldExtension::Quadratic)).unwrap();
+    assert_eq!(FieldExtension::Quadratic, proof.options().field_extension());
+    verify_do_work(pub_inputs, proof).unwrap();
+}
+
+/// The cubic extension of f128 is not implemented, so the prover must refuse to use it.
+#[test]
+fn do_work_with_cubic_extension_is_rejected() {
+    let result = prove_do_work(SEED, 8, options(FieldExtension::Cubic));
+    assert!(matches!(result, Err(Error::Prover(ProverError::UnsupportedFieldExtension(3)))));
+}
+
+// HIDDEN SEED AND BATCH AIRS
+// ================================================================================================
+
+#[test]
+fn hidden_seed_with_quadratic_extension() {
+    let (pub_inputs, proof) =
+        prove_hidden_seed(SEED, 8, true, options(FieldExtension::Quadratic)).unwrap();
+    assert_eq!(FieldExtension::Quadratic, proof.options().field_extension());
+    verify_hidden_seed(pub_inputs, proof).unwrap();
+}
+
+#[test]
+fn batch_with_quadratic_extension() {
+    let seeds = [SEED, [BaseElement::new(7), BaseElement::new(8)]];
+    let (pub_inputs, proof) = prove_batch(&seeds, 8, options(FieldExtension::Quadratic)).unwrap();
+    assert_eq!(FieldExtension::Quadratic, proof.options().field_extension());
+    verify_batch(pub_inputs, proof).unwrap();
+}
+
+// RESCUE128 AIRS
+// ================================================================================================
+
+#[test]
+fn sponge_with_quadratic_extension() {
+    let msg = (1..=5).map(BaseElement::new).collect::<Vec<_>>();
+    let (pub_inputs, proof) = prove_sponge(&msg, options(FieldExtension::Quadratic)).unwrap();
+    assert_eq!(FieldExtension::Quadratic, proof.options().field_extension());
+    verify_sponge(pub_inputs, proof).unwrap();
+}
+
+#[test]
+fn merkle_with_quadratic_extension() {
+    let leaf = Rescue128::hash(b"leaf");
+    let path = (0..3u8).map(|i| Rescue128::hash(&[i])).collect::<Vec<_>>();
+    let (pub_inputs, proof) =
+        prove_membership(leaf, 5, &path, options(FieldExtension::Quadratic)).unwrap();
+    assert_eq!(FieldExtension::Quadratic, proof.options().field_extension());
+    verify_membership(pub_inputs, proof).unwrap();
+}
+
+// PRODUCTION PROFILE
+// ================================================================================================
+
+#[test]
+fn production_profile_selects_extension() {
+    // 128 bits cannot be reached over the 128-bit base field alone
+    let options = production_options(trace_length::<Rescue4x14>(16).unwrap());
+    assert_eq!(FieldExtension::Quadratic, options.field_extension());
+
+    // for lower security levels the base field is sufficient
+    let options = options_for_security(trace_length::<Rescue4x14>(16).unwrap(), 100);
+    assert_eq!(FieldExtension::None, options.field_extension());
+}
+
+#[test]
+fn production_profile_reaches_security_level() {
+    let options = production_options(trace_length::<Rescue4x14>(4).unwrap());
+    let (pub_inputs, proof) = prove_do_work(SEED, 4, options).unwrap();
+    assert!(proof.security_level::<Blake3>(true) >= PRODUCTION_SECURITY_LEVEL);
+    verify_do_work(pub_inputs, proof).unwrap();
+}
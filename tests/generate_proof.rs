@@ -0,0 +1,49 @@
+use std::process::Command;
+
+use stark_test::{prove_do_work, Error};
+use winterfell::{math::fields::f128::BaseElement, FieldExtension, ProofOptions};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+/// Runs the generate_proof binary, and returns its exit code and error output.
+fn generate_proof(args: &[&str]) -> (i32, String) {
+    let output = std::env::temp_dir().join(format!("generate_proof-{}.bin", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_generate_proof"))
+        .arg("--output")
+        .arg(output)
+        .args(args)
+        .output()
+        .unwrap();
+    (output.status.code().unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn blowup_factor_below_constraint_degree_is_rejected() {
+    let options = ProofOptions::new(28, 2, 0, FieldExtension::None, 8, 31);
+    let err = prove_do_work(SEED, 3, options).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+
+    // the command line rejects the blowup factor before any proof is generated
+    for args in [&["-n", "3", "-b", "2"][..], &["-n", "3", "-b", "2", "--hide-seed"]] {
+        let (code, stderr) = generate_proof(args);
+        assert_eq!(2, code, "{stderr}");
+        assert!(stderr.contains("blowup factor must be at least 4"), "{stderr}");
+    }
+}
+
+#[test]
+fn oversized_trace_is_rejected() {
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 8, 31);
+    for iterations in [1 << 62, usize::MAX, 1 << 40] {
+        let err = prove_do_work(SEED, iterations, options.clone()).unwrap_err();
+        assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+    }
+
+    for iterations in ["4611686018427387904", "18446744073709551615"] {
+        for flag in ["--hash-fn=blake3-256", "--hide-seed", "--production"] {
+            let (code, stderr) = generate_proof(&["-n", iterations, flag]);
+            assert_eq!(7, code, "{stderr}");
+            assert!(stderr.contains("computation does not fit into a trace"), "{stderr}");
+        }
+    }
+}
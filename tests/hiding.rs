@@ -0,0 +1,85 @@
+use stark_test::hiding::{
+    blinding_steps, commit_to_seed, prove_hidden_seed, trace_length, verify_hidden_seed_with,
+    HiddenSeedPublicInputs,
+};
+use stark_test::{hash_chain, result_step, verify_bundle, AirId, Error, ProofBundle, Rescue4x14};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    FieldExtension, ProofOptions,
+};
+
+mod common;
+use common::{is_inconsistent, options, policy};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+#[test]
+fn blinding_rows_cover_all_openings() {
+    // 28 queries open 8 points of the first FRI layer each, and every point depends on two rows
+    assert_eq!(2 * 28 * 8 + 2, blinding_steps(&options()));
+
+    for iterations in [1, 3, 8] {
+        let length = trace_length(iterations, blinding_steps(&options())).unwrap();
+        let blinding_rows = length - result_step::<Rescue4x14>(iterations) - 1;
+        assert!(blinding_rows >= blinding_steps(&options()), "{iterations} iterations");
+    }
+}
+
+#[test]
+fn proofs_of_hidden_seeds_verify() {
+    for (iterations, commit_seed) in [(1, false), (1, true), (3, false), (3, true)] {
+        let (pub_inputs, proof) =
+            prove_hidden_seed(SEED, iterations, commit_seed, options()).unwrap();
+        assert_eq!(hash_chain(SEED, iterations), pub_inputs.result);
+        assert_eq!(commit_seed.then(|| commit_to_seed(SEED)), pub_inputs.seed_commitment);
+        assert!(verify_hidden_seed_with(pub_inputs, proof, &policy()).is_ok());
+    }
+}
+
+#[test]
+fn wrong_result_is_rejected() {
+    let (mut pub_inputs, proof) = prove_hidden_seed(SEED, 3, true, options()).unwrap();
+    pub_inputs.result[1] += BaseElement::ONE;
+    assert!(is_inconsistent(verify_hidden_seed_with(pub_inputs, proof, &policy())));
+}
+
+#[test]
+fn wrong_commitment_is_rejected() {
+    let (pub_inputs, proof) = prove_hidden_seed(SEED, 3, true, options()).unwrap();
+
+    let other_seed = [SEED[1], SEED[0]];
+    let wrong =
+        HiddenSeedPublicInputs { seed_commitment: Some(commit_to_seed(other_seed)), ..pub_inputs };
+    assert!(is_inconsistent(verify_hidden_seed_with(wrong, proof, &policy())));
+
+    // a proof without a commitment does not attest to any commitment
+    let (pub_inputs, proof_without) = prove_hidden_seed(SEED, 3, false, options()).unwrap();
+    let claimed =
+        HiddenSeedPublicInputs { seed_commitment: Some(commit_to_seed(SEED)), ..pub_inputs };
+    assert!(is_inconsistent(verify_hidden_seed_with(claimed, proof_without, &policy())));
+}
+
+#[test]
+fn bundle_round_trip() {
+    let (pub_inputs, proof) = prove_hidden_seed(SEED, 3, true, options()).unwrap();
+    let bytes = ProofBundle::new(pub_inputs, proof.clone()).to_bytes();
+
+    let bundle = ProofBundle::<HiddenSeedPublicInputs>::from_bytes(&bytes).unwrap();
+    assert_eq!(pub_inputs, bundle.pub_inputs);
+    assert_eq!(proof.to_bytes(), bundle.proof.to_bytes());
+
+    let (air, _) = verify_bundle(&bytes, &policy()).unwrap();
+    assert_eq!(AirId::HiddenSeed, air);
+}
+
+#[test]
+fn invalid_options_are_rejected() {
+    // constraints of degree 3 switched off by a periodic column need a blowup factor of 4
+    let small_blowup = ProofOptions::new(28, 2, 0, FieldExtension::None, 8, 31);
+    let err = prove_hidden_seed(SEED, 3, false, small_blowup).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+
+    // the blinding rows do not fit into a trace after the hash chain
+    let err = prove_hidden_seed(SEED, usize::MAX, false, options()).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+}
@@ -0,0 +1,58 @@
+use stark_test::hiding::prove_hidden_seed;
+use stark_test::merkle::prove_membership;
+use stark_test::utils::rescue::Hash;
+use stark_test::{
+    prove_do_work_bundle, read_bundle_proof, AirId, HashFunction, ProofBundle, ProofSize,
+};
+use winterfell::{math::fields::f128::BaseElement, FieldExtension, ProofOptions};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+#[test]
+fn size_breakdown_adds_up_to_proof_size() {
+    let all_options = [
+        ProofOptions::new(32, 8, 0, FieldExtension::None, 8, 31),
+        ProofOptions::new(28, 16, 8, FieldExtension::Quadratic, 4, 7),
+        ProofOptions::new(40, 4, 0, FieldExtension::None, 2, 0),
+    ];
+    for options in all_options {
+        let bundle =
+            prove_do_work_bundle(SEED, 32, HashFunction::Blake3_256, options.clone()).unwrap();
+        let size = ProofSize::of(&bundle.proof);
+        assert_eq!(bundle.proof.to_bytes().len(), size.total());
+        assert_eq!(8, size.pow_nonce);
+        assert_eq!(1, size.gkr_proof);
+
+        // the remainder is sent as at most remainder_max_degree + 1 coefficients, each taking
+        // 16 bytes per base field element
+        let coefficient_size = 16 * options.field_extension().degree() as usize;
+        let max_coefficients = options.to_fri_options().remainder_max_degree() + 1;
+        let num_coefficients = (size.fri_remainder - 2) / coefficient_size;
+        assert_eq!(num_coefficients * coefficient_size + 2, size.fri_remainder);
+        assert!((1..=max_coefficients).contains(&num_coefficients));
+    }
+}
+
+#[test]
+fn proofs_are_read_from_bundles_of_any_air() {
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 8, 31);
+
+    let bundle = prove_do_work_bundle(SEED, 8, HashFunction::Sha3_256, options.clone()).unwrap();
+    let (header, proof) = read_bundle_proof(&bundle.to_bytes()).unwrap();
+    assert_eq!((AirId::DoWork, HashFunction::Sha3_256), (header.air, header.hash_fn));
+    assert_eq!(bundle.proof.to_bytes(), proof.to_bytes());
+
+    let (pub_inputs, proof) = prove_hidden_seed(SEED, 8, true, options.clone()).unwrap();
+    let bytes = ProofBundle::new(pub_inputs, proof.clone()).to_bytes();
+    let (header, read_proof) = read_bundle_proof(&bytes).unwrap();
+    assert_eq!(AirId::HiddenSeed, header.air);
+    assert_eq!(proof.to_bytes(), read_proof.to_bytes());
+
+    let leaf = Hash::new(BaseElement::new(1), BaseElement::new(2));
+    let path = vec![Hash::new(BaseElement::new(3), BaseElement::new(4)); 3];
+    let (pub_inputs, proof) = prove_membership(leaf, 5, &path, options).unwrap();
+    let bytes = ProofBundle::new(pub_inputs, proof.clone()).to_bytes();
+    let (header, read_proof) = read_bundle_proof(&bytes).unwrap();
+    assert_eq!(AirId::Merkle, header.air);
+    assert_eq!(proof.to_bytes(), read_proof.to_bytes());
+}
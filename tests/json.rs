@@ -0,0 +1,118 @@
+use serde_json::json;
+use stark_test::json::ProofMetadata;
+use stark_test::{prove_do_work_bundle, AirId, HashFunction, PublicInputs};
+use winterfell::{math::fields::f128::BaseElement, FieldExtension, ProofOptions};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+/// Modulus of the f128 field: 2^128 - 45 * 2^40 + 1.
+const MODULUS: u128 = 340282366920938463463374557953744961537;
+
+fn public_inputs() -> PublicInputs {
+    PublicInputs { seed: SEED, result: [BaseElement::new(7), BaseElement::new(8)], iterations: 16 }
+}
+
+#[test]
+fn public_inputs_round_trip() {
+    let pub_inputs = public_inputs();
+    let value = serde_json::to_value(pub_inputs).unwrap();
+    assert_eq!(json!({ "seed": ["42", "43"], "result": ["7", "8"], "iterations": 16 }), value);
+    assert_eq!(pub_inputs, serde_json::from_value::<PublicInputs>(value).unwrap());
+}
+
+#[test]
+fn public_inputs_accept_hex_elements() {
+    let value = json!({ "seed": ["0x2a", "0x2B"], "result": ["7", "0x8"], "iterations": 16 });
+    assert_eq!(public_inputs(), serde_json::from_value::<PublicInputs>(value).unwrap());
+
+    let largest = (MODULUS - 1).to_string();
+    let value = json!({ "seed": [largest, "0"], "result": ["0", "0"], "iterations": 1 });
+    let pub_inputs = serde_json::from_value::<PublicInputs>(value).unwrap();
+    assert_eq!(BaseElement::new(MODULUS - 1), pub_inputs.seed[0]);
+}
+
+#[test]
+fn public_inputs_reject_non_canonical_elements() {
+    let invalid = [
+        json!([MODULUS.to_string(), "0"]),
+        json!([format!("{:#x}", MODULUS), "0"]),
+        json!([u128::MAX.to_string(), "0"]),
+        json!(["-1", "0"]),
+        json!(["0xg", "0"]),
+        json!(["+7", "0"]),
+        json!(["0x+7", "0"]),
+        json!(["007", "0"]),
+        json!(["00", "0"]),
+        json!(["0x0001", "0"]),
+        json!(["0x00", "0"]),
+        json!(["", "0"]),
+        json!([42, 43]),
+        json!(["42"]),
+        json!(["42", "43", "44"]),
+    ];
+    for seed in invalid {
+        let value = json!({ "seed": seed, "result": ["7", "8"], "iterations": 16 });
+        assert!(serde_json::from_value::<PublicInputs>(value.clone()).is_err(), "{value}");
+    }
+}
+
+#[test]
+fn elements_round_trip() {
+    let largest = (MODULUS - 1).to_string();
+    for element in ["0", "1", "7", "42", "1000", largest.as_str()] {
+        let value = json!({ "seed": [element, "0"], "result": ["0", element], "iterations": 1 });
+        let pub_inputs = serde_json::from_value::<PublicInputs>(value.clone()).unwrap();
+        assert_eq!(value, serde_json::to_value(pub_inputs).unwrap());
+    }
+
+    // hexadecimal elements are written back in decimal
+    for (hex, decimal) in [("0x0", "0"), ("0x2a", "42"), ("0x2B", "43")] {
+        let value = json!({ "seed": [hex, "0"], "result": ["0", "0"], "iterations": 1 });
+        let pub_inputs = serde_json::from_value::<PublicInputs>(value).unwrap();
+        assert_eq!(json!([decimal, "0"]), serde_json::to_value(pub_inputs).unwrap()["seed"]);
+    }
+}
+
+#[test]
+fn proof_metadata_round_trip() {
+    let options = ProofOptions::new(28, 8, 4, FieldExtension::Quadratic, 4, 31);
+    let bundle = prove_do_work_bundle(SEED, 4, HashFunction::Sha3_256, options.clone()).unwrap();
+
+    let metadata = ProofMetadata::from_bundle(&bundle);
+    assert_eq!(AirId::DoWork, metadata.air);
+    assert_eq!(options, metadata.options);
+
+    let value = serde_json::to_value(&metadata).unwrap();
+    assert_eq!(json!("do-work"), value["air"]);
+    assert_eq!(json!("sha3-256"), value["hash_fn"]);
+    assert_eq!(json!(bundle.proof.trace_info().length()), value["trace_length"]);
+    assert_eq!(json!("quadratic"), value["options"]["field_extension"]);
+    assert_eq!(metadata, serde_json::from_value::<ProofMetadata>(value).unwrap());
+}
+
+#[test]
+fn proof_metadata_rejects_invalid_options() {
+    let metadata = json!({
+        "air": "do-work",
+        "hash_fn": "blake3-256",
+        "trace_width": 4,
+        "trace_length": 64,
+        "options": {
+            "num_queries": 28,
+            "blowup_factor": 6,
+            "grinding_factor": 0,
+            "field_extension": "none",
+            "folding_factor": 8,
+            "remainder_max_degree": 31,
+        },
+    });
+    assert!(serde_json::from_value::<ProofMetadata>(metadata.clone()).is_err());
+
+    let mut unknown_air = metadata;
+    unknown_air["air"] = json!("fibonacci");
+    unknown_air["options"]["blowup_factor"] = json!(8);
+    assert!(serde_json::from_value::<ProofMetadata>(unknown_air.clone()).is_err());
+
+    unknown_air["air"] = json!("merkle");
+    assert!(serde_json::from_value::<ProofMetadata>(unknown_air).is_ok());
+}
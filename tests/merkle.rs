@@ -0,0 +1,116 @@
+use stark_test::hiding::blinding_steps;
+use stark_test::merkle::{
+    compute_root, prove_membership, trace_length, verify_membership_with, MerklePublicInputs,
+    MAX_DEPTH,
+};
+use stark_test::utils::rescue::Hash;
+use stark_test::Error;
+use winterfell::{math::fields::f128::BaseElement, FieldExtension, ProofOptions};
+
+mod common;
+use common::{is_inconsistent, options, policy};
+
+fn node(value: u128) -> Hash {
+    Hash::new(BaseElement::new(value), BaseElement::new(value + 1))
+}
+
+fn path(depth: usize) -> Vec<Hash> {
+    (0..depth as u128).map(|level| node(2 * level + 10)).collect()
+}
+
+#[test]
+fn valid_paths_verify() {
+    let leaf = node(1);
+    for (index, depth) in [(0, 1), (1, 1), (0, 3), (5, 3), (6, 5)] {
+        let path = path(depth);
+        let (pub_inputs, proof) = prove_membership(leaf, index, &path, options()).unwrap();
+        let root = compute_root(leaf, index, &path);
+        assert_eq!(MerklePublicInputs { root, depth }, pub_inputs);
+        let result = verify_membership_with(pub_inputs, proof, &policy());
+        assert!(result.is_ok(), "index {index} at depth {depth}: {result:?}");
+    }
+}
+
+#[test]
+fn wrong_root_is_rejected() {
+    let (mut pub_inputs, proof) = prove_membership(node(1), 5, &path(3), options()).unwrap();
+    pub_inputs.root = compute_root(node(2), 5, &path(3));
+    assert!(is_inconsistent(verify_membership_with(pub_inputs, proof, &policy())));
+}
+
+#[test]
+fn wrong_sibling_is_rejected() {
+    let root = compute_root(node(1), 5, &path(3));
+
+    // a proof for a path with a wrong sibling attests to a different root
+    let mut wrong_path = path(3);
+    wrong_path[1] = node(100);
+    let (mut pub_inputs, proof) = prove_membership(node(1), 5, &wrong_path, options()).unwrap();
+    assert_ne!(root, pub_inputs.root);
+    pub_inputs.root = root;
+    assert!(is_inconsistent(verify_membership_with(pub_inputs, proof, &policy())));
+}
+
+#[test]
+fn depth_zero_is_rejected() {
+    let err = prove_membership(node(1), 0, &[], options()).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+
+    let (mut pub_inputs, proof) = prove_membership(node(1), 0, &path(1), options()).unwrap();
+    pub_inputs.depth = 0;
+    let err = verify_membership_with(pub_inputs, proof, &policy()).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+}
+
+#[test]
+fn maximum_depth_is_supported() {
+    // every index fits into a tree of the maximum depth
+    let max_path = path(MAX_DEPTH);
+    let (pub_inputs, proof) = prove_membership(node(1), u64::MAX, &max_path, options()).unwrap();
+    assert_eq!(compute_root(node(1), u64::MAX, &max_path), pub_inputs.root);
+    assert!(verify_membership_with(pub_inputs, proof, &policy()).is_ok());
+
+    let err = prove_membership(node(1), 0, &path(MAX_DEPTH + 1), options()).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+}
+
+#[test]
+fn index_beyond_tree_is_rejected() {
+    let err = prove_membership(node(1), 8, &path(3), options()).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+
+    // the direction bits of a proof are fixed, so a leaf cannot be moved to another index
+    let root = compute_root(node(1), 4, &path(3));
+    let (mut pub_inputs, proof) = prove_membership(node(1), 5, &path(3), options()).unwrap();
+    pub_inputs.root = root;
+    assert!(is_inconsistent(verify_membership_with(pub_inputs, proof, &policy())));
+}
+
+#[test]
+fn blowup_factor_below_constraint_degree_is_rejected() {
+    // constraints of degree 5 need a blowup factor of at least 8
+    let options = ProofOptions::new(28, 4, 0, FieldExtension::None, 8, 31);
+    let err = prove_membership(node(1), 5, &path(3), options).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+}
+
+#[test]
+fn blinding_rows_cover_all_openings() {
+    for depth in [1, 3, 5, MAX_DEPTH] {
+        let length = trace_length(depth, blinding_steps(&options())).unwrap();
+        let root_step = MerklePublicInputs { root: node(0), depth }.root_step();
+        let blinding_rows = length - root_step - 1;
+        assert!(blinding_rows >= blinding_steps(&options()), "depth {depth}");
+    }
+}
+
+#[test]
+fn proofs_of_the_same_path_differ() {
+    // the random rows after the root change every commitment and opening
+    let (pub_inputs, proof) = prove_membership(node(1), 5, &path(3), options()).unwrap();
+    let (other_pub_inputs, other_proof) =
+        prove_membership(node(1), 5, &path(3), options()).unwrap();
+    assert_eq!(pub_inputs, other_pub_inputs);
+    assert_ne!(proof.to_bytes(), other_proof.to_bytes());
+    assert!(verify_membership_with(other_pub_inputs, other_proof, &policy()).is_ok());
+}
@@ -0,0 +1,135 @@
+use stark_test::batch::BatchProver;
+use stark_test::debug::{check_transition_degrees, mock_prove, ConstraintError, MockProofError};
+use stark_test::hiding::{self, HiddenSeedProver};
+use stark_test::merkle::{self, MerkleProver};
+use stark_test::sponge::{self, SpongeProver};
+use stark_test::utils::rescue::Hash;
+use stark_test::{batch, build_trace, Blake3, DoWorkProver, Rescue4x14, Rescue6x7};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement, ToElements},
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, Prover, Trace,
+    TraceInfo, TraceTable, TransitionConstraintDegree,
+};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+fn options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 8, 31)
+}
+
+#[test]
+fn valid_traces_pass_for_every_prover() {
+    let trace = build_trace::<Rescue4x14>(SEED, 5);
+    let prover = DoWorkProver::<Rescue4x14, Blake3>::new(options(), 5);
+    assert_eq!(prover.get_pub_inputs(&trace), mock_prove(&prover, &trace).unwrap());
+
+    let trace = build_trace::<Rescue6x7>(SEED, 5);
+    let prover = DoWorkProver::<Rescue6x7, Blake3>::new(options(), 5);
+    assert_eq!(prover.get_pub_inputs(&trace), mock_prove(&prover, &trace).unwrap());
+
+    let msg: Vec<BaseElement> = (0..7).map(BaseElement::new).collect();
+    let trace = sponge::build_trace(&msg);
+    let prover = SpongeProver::new(options(), msg.len());
+    assert_eq!(prover.get_pub_inputs(&trace), mock_prove(&prover, &trace).unwrap());
+
+    let leaf = Hash::new(BaseElement::new(1), BaseElement::new(2));
+    let path: Vec<Hash> =
+        (3..6).map(|i| Hash::new(BaseElement::new(i), BaseElement::new(i + 1))).collect();
+    let trace = merkle::build_trace(leaf, 5, &path, 4, &mut rand::thread_rng());
+    let prover = MerkleProver::new(options(), path.len());
+    assert_eq!(prover.get_pub_inputs(&trace), mock_prove(&prover, &trace).unwrap());
+
+    let trace = hiding::build_trace(SEED, 3, 4, &mut rand::thread_rng());
+    let prover = HiddenSeedProver::new(options(), 3, true);
+    assert_eq!(prover.get_pub_inputs(&trace), mock_prove(&prover, &trace).unwrap());
+
+    let trace = batch::build_trace::<Rescue4x14>(&[SEED, [SEED[1], SEED[0]]], 3);
+    let prover = BatchProver::<Rescue4x14>::new(options(), 2, 3);
+    assert_eq!(prover.get_pub_inputs(&trace), mock_prove(&prover, &trace).unwrap());
+}
+
+#[test]
+fn invalid_trace_reports_constraint_failure() {
+    let mut trace = build_trace::<Rescue4x14>(SEED, 5);
+    let prover = DoWorkProver::<Rescue4x14, Blake3>::new(options(), 5);
+    trace.set(2, 21, trace.get(2, 21) + BaseElement::ONE);
+
+    let err = mock_prove(&prover, &trace).unwrap_err();
+    assert!(
+        matches!(err, MockProofError::Constraint(ConstraintError::Transition { step: 20, .. })),
+        "unexpected failure: {err}"
+    );
+}
+
+// CUBE CHAIN AIR
+// ================================================================================================
+
+/// AIR for `x_{i+1} = x_i^3` whose transition constraint is declared with an arbitrary degree.
+struct CubeAir {
+    context: AirContext<BaseElement>,
+    start: BaseElement,
+}
+
+struct CubeInputs {
+    start: BaseElement,
+    declared_degree: usize,
+}
+
+impl ToElements<BaseElement> for CubeInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![self.start]
+    }
+}
+
+impl Air for CubeAir {
+    type BaseField = BaseElement;
+    type PublicInputs = CubeInputs;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: CubeInputs, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(pub_inputs.declared_degree)];
+        let context = AirContext::new(trace_info, degrees, 1, options);
+        CubeAir { context, start: pub_inputs.start }
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - frame.current()[0].exp(3u32.into());
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, self.start)]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+fn cube_air(declared_degree: usize) -> (CubeAir, TraceTable<BaseElement>) {
+    let start = BaseElement::new(3);
+    let mut trace = TraceTable::new(1, 32);
+    trace.fill(|state| state[0] = start, |_, state| state[0] = state[0].exp(3u32.into()));
+    let pub_inputs = CubeInputs { start, declared_degree };
+    (CubeAir::new(trace.info().clone(), pub_inputs, options()), trace)
+}
+
+#[test]
+fn declared_degrees_are_checked() {
+    let (air, trace) = cube_air(3);
+    check_transition_degrees(&air, &trace).unwrap();
+
+    // the divided constraint has degree 3 * 31 - 31 = 62 over a trace of 32 steps
+    for declared_degree in [2, 4] {
+        let (air, trace) = cube_air(declared_degree);
+        let expected = vec![(declared_degree - 1) * 31];
+        let err = check_transition_degrees(&air, &trace).unwrap_err();
+        assert_eq!(MockProofError::DegreeMismatch { expected, actual: vec![62] }, err);
+        assert!(err.to_string().starts_with("degrees of transition constraints [0] differ"));
+    }
+}
@@ -0,0 +1,60 @@
+use stark_test::hiding::commit_to_seed;
+use stark_test::{
+    build_trace, hash, hash_chain, prove_do_work, result_step, verify_do_work, AlgebraicPermutation,
+    PublicInputs, Rescue4x14,
+};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    FieldExtension, ProofOptions, Trace,
+};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+#[test]
+fn hash_chain_matches_trace_result() {
+    for iterations in 1..=9 {
+        let trace = build_trace::<Rescue4x14>(SEED, iterations);
+        let step = result_step::<Rescue4x14>(iterations);
+        let expected = [trace.get(0, step), trace.get(1, step)];
+        assert_eq!(expected, hash_chain(SEED, iterations), "iterations: {iterations}");
+    }
+}
+
+#[test]
+fn hash_chain_matches_every_cycle_of_trace() {
+    let trace = build_trace::<Rescue4x14>(SEED, 16);
+    for cycle in 0..trace.length() / Rescue4x14::CYCLE_LENGTH {
+        // every cycle starts with the previous link of the chain followed by zeros
+        let step = cycle * Rescue4x14::CYCLE_LENGTH;
+        let link = hash_chain(SEED, cycle);
+        let row = [trace.get(0, step), trace.get(1, step), trace.get(2, step), trace.get(3, step)];
+        assert_eq!([link[0], link[1], BaseElement::ZERO, BaseElement::ZERO], row);
+    }
+}
+
+#[test]
+fn hash_chain_composes_hashes() {
+    assert_eq!(SEED, hash_chain(SEED, 0));
+    assert_eq!(hash(SEED), hash_chain(SEED, 1));
+    assert_eq!(hash(hash_chain(SEED, 4)), hash_chain(SEED, 5));
+    assert_eq!(hash_chain(hash_chain(SEED, 3), 4), hash_chain(SEED, 7));
+    assert_eq!(hash(SEED), commit_to_seed(SEED));
+}
+
+#[test]
+fn hash_depends_on_both_input_elements() {
+    let digest = hash(SEED);
+    assert_ne!(digest, hash([SEED[1], SEED[0]]));
+    assert_ne!(digest, hash([SEED[0], SEED[1] + BaseElement::ONE]));
+    assert_ne!(hash([BaseElement::ZERO; 2]), [BaseElement::ZERO; 2]);
+}
+
+#[test]
+fn precomputed_result_is_accepted_by_verifier() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 8, 31);
+    let (pub_inputs, proof) = prove_do_work(SEED, 12, options).unwrap();
+    assert_eq!(hash_chain(SEED, 12), pub_inputs.result);
+
+    let expected = PublicInputs { seed: SEED, result: hash_chain(SEED, 12), iterations: 12 };
+    verify_do_work(expected, proof).unwrap();
+}
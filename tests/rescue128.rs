@@ -0,0 +1,198 @@
+use stark_test::utils::rescue::{Hash, Rescue128};
+use winterfell::{
+    crypto::{DefaultRandomCoin, ElementHasher, Hasher, MerkleTree, RandomCoin},
+    math::{fields::f128::BaseElement, FieldElement},
+};
+
+fn hash(v1: u128, v2: u128) -> Hash {
+    Hash::new(BaseElement::new(v1), BaseElement::new(v2))
+}
+
+/// Returns the message `[1, 2, ..., n]`.
+fn message(n: u128) -> Vec<BaseElement> {
+    (1..=n).map(BaseElement::new).collect()
+}
+
+// KNOWN-ANSWER TESTS
+// ================================================================================================
+
+#[test]
+fn hash_bytes_known_answers() {
+    let cases: [(&[u8], Hash); 6] = [
+        (
+            b"",
+            hash(133822051968265890378892855941285182899, 258583819390616089367218576966327881724),
+        ),
+        (
+            b"\x00",
+            hash(36905169176053200396796618439183473525, 212014173624393290406068586869070386026),
+        ),
+        (
+            b"abc",
+            hash(237174353036325613703712688302512815732, 145652587648065725832046801744194014082),
+        ),
+        (
+            &[7; 15],
+            hash(240183107517977660591305257196395485028, 237578868875493429304402011434568767156),
+        ),
+        (
+            &[7; 16],
+            hash(270548718117587650752092276019072083148, 6764387934161300620707142656487094129),
+        ),
+        (
+            &[0xff; 45],
+            hash(110451967555604818431093148261950441464, 287966118334871857148935720010329784341),
+        ),
+    ];
+
+    for (input, expected) in cases {
+        assert_eq!(expected, Rescue128::hash(input), "input: {input:?}");
+    }
+}
+
+/// Test vectors for [Rescue128::digest] over messages `[1, 2, ..., n]`.
+#[test]
+fn digest_known_answers() {
+    let cases: [(u128, Hash); 6] = [
+        (
+            0,
+            hash(154188719388486945513206054252529397465, 199228237911110826039242077326141901480),
+        ),
+        (
+            1,
+            hash(133822051968265890378892855941285182899, 258583819390616089367218576966327881724),
+        ),
+        (
+            3,
+            hash(170647055800157670427065434667433630368, 48444240528795319294985685231339637377),
+        ),
+        (
+            4,
+            hash(201422514053380344497962859134078581468, 56773518511215665834524651426856250175),
+        ),
+        (
+            5,
+            hash(213649430011526913174180883851241503591, 278410651143008990915259321850500582237),
+        ),
+        (
+            8,
+            hash(144703244946614967257438709608347808978, 323163624867202423560370606913573284460),
+        ),
+    ];
+
+    for (n, expected) in cases {
+        assert_eq!(expected, Rescue128::digest(&message(n)), "message length: {n}");
+    }
+}
+
+#[test]
+fn merge_known_answers() {
+    let a = Rescue128::hash(b"a");
+    let b = Rescue128::hash(b"b");
+    let c = Rescue128::hash(b"c");
+
+    assert_eq!(
+        hash(77709533169928540561784701024805747163, 13797801826204844538822805225250692976),
+        Rescue128::merge(&[a, b])
+    );
+    assert_eq!(
+        hash(20754923774783770210102260201002559759, 276639734029376818526590360544185711804),
+        Rescue128::merge_many(&[a, b, c])
+    );
+    assert_eq!(
+        hash(248290823377374760281680299369213668111, 219582316286354844462288352431264048468),
+        Rescue128::merge_with_int(a, 42)
+    );
+}
+
+// CONSISTENCY TESTS
+// ================================================================================================
+
+#[test]
+fn digest_distinguishes_trailing_zeros() {
+    let zero = BaseElement::ZERO;
+    let inputs: [&[BaseElement]; 6] =
+        [&[], &[zero], &[zero; 3], &[zero; 4], &[zero; 5], &[zero; 8]];
+    for (i, a) in inputs.iter().enumerate() {
+        for b in inputs.iter().skip(i + 1) {
+            assert_ne!(Rescue128::digest(a), Rescue128::digest(b), "{a:?} and {b:?} collide");
+        }
+    }
+}
+
+#[test]
+fn digest_distinguishes_padding_from_message() {
+    // [1, 2, 3] is padded to [1, 2, 3, 1], which must not collide with the unpadded message
+    let one = BaseElement::ONE;
+    let padded = [BaseElement::new(1), BaseElement::new(2), BaseElement::new(3), one];
+    assert_ne!(Rescue128::digest(&message(3)), Rescue128::digest(&padded));
+}
+
+#[test]
+fn streaming_agrees_with_digest() {
+    for n in 0..13 {
+        let data = message(n);
+        for split in 0..=data.len() {
+            let mut hasher = Rescue128::new();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(Rescue128::digest(&data), hasher.finalize(), "n: {n}, split: {split}");
+        }
+    }
+}
+
+#[test]
+fn hash_bytes_distinguishes_trailing_zeros() {
+    let inputs: [&[u8]; 5] = [b"", b"\x00", b"\x00\x00", &[0; 15], &[0; 16]];
+    for (i, a) in inputs.iter().enumerate() {
+        for b in inputs.iter().skip(i + 1) {
+            assert_ne!(Rescue128::hash(a), Rescue128::hash(b), "{a:?} and {b:?} collide");
+        }
+    }
+}
+
+#[test]
+fn merge_many_agrees_with_merge() {
+    let a = Rescue128::hash(b"a");
+    let b = Rescue128::hash(b"b");
+    assert_eq!(Rescue128::merge(&[a, b]), Rescue128::merge_many(&[a, b]));
+}
+
+#[test]
+fn merge_with_int_depends_on_value() {
+    let seed = Rescue128::hash(b"seed");
+    assert_ne!(Rescue128::merge_with_int(seed, 1), Rescue128::merge_with_int(seed, 2));
+    assert_ne!(Rescue128::merge_with_int(seed, 0), seed);
+}
+
+#[test]
+fn hash_elements_agrees_with_digest() {
+    let elements = [BaseElement::new(1), BaseElement::new(2), BaseElement::new(3)];
+    assert_eq!(Rescue128::digest(&elements), Rescue128::hash_elements(&elements));
+}
+
+// WINTERFELL INTEGRATION
+// ================================================================================================
+
+#[test]
+fn merkle_tree_with_rescue128() {
+    let leaves = (0..8u8).map(|i| Rescue128::hash(&[i])).collect::<Vec<_>>();
+    let tree = MerkleTree::<Rescue128>::new(leaves.clone()).unwrap();
+
+    let (leaf, path) = tree.prove(5).unwrap();
+    assert_eq!(leaves[5], leaf);
+    MerkleTree::<Rescue128>::verify(*tree.root(), 5, leaf, &path).unwrap();
+    assert!(MerkleTree::<Rescue128>::verify(*tree.root(), 4, leaf, &path).is_err());
+}
+
+#[test]
+fn random_coin_with_rescue128() {
+    let seed = [BaseElement::new(42), BaseElement::new(43)];
+    let mut coin1 = DefaultRandomCoin::<Rescue128>::new(&seed);
+    let mut coin2 = DefaultRandomCoin::<Rescue128>::new(&seed);
+
+    let a: BaseElement = coin1.draw().unwrap();
+    let b: BaseElement = coin2.draw().unwrap();
+    assert_eq!(a, b);
+    assert_ne!(BaseElement::ZERO, a);
+}
@@ -0,0 +1,91 @@
+use stark_test::utils::rescue::Hash;
+use stark_test::{
+    batch, merkle, prove_do_work, sponge, verify_do_work, verify_do_work_with, Blake3, Error,
+    SecurityError, SecurityLevel, SecurityPolicy,
+};
+use winterfell::{math::fields::f128::BaseElement, FieldExtension, ProofOptions};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+fn options(num_queries: usize) -> ProofOptions {
+    ProofOptions::new(num_queries, 8, 0, FieldExtension::None, 8, 31)
+}
+
+#[test]
+fn verifier_reports_security_level() {
+    let (pub_inputs, proof) = prove_do_work(SEED, 4, options(32)).unwrap();
+    let expected = SecurityLevel {
+        conjectured: proof.security_level::<Blake3>(true),
+        proven: proof.security_level::<Blake3>(false),
+    };
+    assert_eq!(expected, verify_do_work(pub_inputs, proof).unwrap());
+}
+
+#[test]
+fn rejection_reports_actual_security_level() {
+    let (pub_inputs, proof) = prove_do_work(SEED, 4, options(20)).unwrap();
+    let actual = SecurityLevel::of::<Blake3>(&proof);
+
+    let result = verify_do_work(pub_inputs, proof.clone());
+    let expected = SecurityError::InsufficientConjectured { required: 95, actual };
+    assert!(matches!(result, Err(Error::Security(err)) if err == expected));
+
+    let policy = SecurityPolicy::MinProven(actual.proven + 1);
+    let result = verify_do_work_with::<Blake3>(pub_inputs, proof.clone(), &policy);
+    let expected = SecurityError::InsufficientProven { required: actual.proven + 1, actual };
+    assert!(matches!(result, Err(Error::Security(err)) if err == expected));
+
+    // a weaker policy accepts the same proof
+    let policy = SecurityPolicy::MinConjectured(actual.conjectured);
+    assert_eq!(actual, verify_do_work_with::<Blake3>(pub_inputs, proof, &policy).unwrap());
+}
+
+#[test]
+fn rejection_exposes_security_error_as_source() {
+    let (pub_inputs, proof) = prove_do_work(SEED, 4, options(20)).unwrap();
+    let err = verify_do_work(pub_inputs, proof).unwrap_err();
+    let source = std::error::Error::source(&err).expect("security error is the source");
+    let source = source.downcast_ref::<SecurityError>().unwrap();
+    assert!(matches!(source, SecurityError::InsufficientConjectured { required: 95, .. }));
+}
+
+#[test]
+fn allow_list_accepts_only_listed_options() {
+    let (pub_inputs, proof) = prove_do_work(SEED, 4, options(32)).unwrap();
+    let actual = SecurityLevel::of::<Blake3>(&proof);
+
+    let policy = SecurityPolicy::AllowList(vec![options(40), options(32)]);
+    assert_eq!(actual, verify_do_work_with::<Blake3>(pub_inputs, proof.clone(), &policy).unwrap());
+
+    let policy = SecurityPolicy::AllowList(vec![options(40)]);
+    let result = verify_do_work_with::<Blake3>(pub_inputs, proof, &policy);
+    let expected = SecurityError::UnacceptableOptions { actual };
+    assert!(matches!(result, Err(Error::Security(err)) if err == expected));
+}
+
+#[test]
+fn every_air_accepts_a_security_policy() {
+    // 20 queries give too little security for the default policy
+    let policy = SecurityPolicy::MinConjectured(0);
+    let strict = SecurityPolicy::MinConjectured(200);
+    let is_insufficient = |result| matches!(result, Err(Error::Security(_)));
+
+    let msg: Vec<BaseElement> = (0..5).map(BaseElement::new).collect();
+    let (pub_inputs, proof) = sponge::prove_sponge(&msg, options(20)).unwrap();
+    assert!(sponge::verify_sponge_with(pub_inputs, proof.clone(), &policy).is_ok());
+    assert!(is_insufficient(sponge::verify_sponge(pub_inputs, proof.clone())));
+    assert!(is_insufficient(sponge::verify_sponge_with(pub_inputs, proof, &strict)));
+
+    let leaf = Hash::new(BaseElement::new(1), BaseElement::new(2));
+    let path = [Hash::new(BaseElement::new(3), BaseElement::new(4))];
+    let (pub_inputs, proof) = merkle::prove_membership(leaf, 1, &path, options(20)).unwrap();
+    assert!(merkle::verify_membership_with(pub_inputs, proof.clone(), &policy).is_ok());
+    assert!(is_insufficient(merkle::verify_membership(pub_inputs, proof.clone())));
+    assert!(is_insufficient(merkle::verify_membership_with(pub_inputs, proof, &strict)));
+
+    let (pub_inputs, proof) = batch::prove_batch(&[SEED], 2, options(20)).unwrap();
+    let result = batch::verify_batch_with(pub_inputs.clone(), proof.clone(), &policy);
+    assert!(result.is_ok());
+    assert!(is_insufficient(batch::verify_batch(pub_inputs.clone(), proof.clone())));
+    assert!(is_insufficient(batch::verify_batch_with(pub_inputs, proof, &strict)));
+}
@@ -0,0 +1,602 @@
+use std::ops::Range;
+use std::sync::OnceLock;
+
+use proptest::prelude::*;
+use proptest::sample::Index;
+use stark_test::batch::prove_batch;
+use stark_test::hiding::prove_hidden_seed;
+use stark_test::merkle::prove_membership;
+use stark_test::sponge::prove_sponge;
+use stark_test::utils::rescue::Hash;
+use stark_test::{
+    prove_do_work, trace_length, verify_bundle, verify_do_work_with, AirId, AlgebraicPermutation,
+    Blake3, BundleError, DoWorkProver, Error, HashFunction, ProofBundle, ProofSize, PublicInputs,
+    Rescue4x14, TrainAir, BUNDLE_HEADER_SIZE,
+};
+use winter_air::{proof::Context, PartitionOptions};
+use winter_utils::{Deserializable, Serializable, SliceReader};
+use winterfell::{
+    crypto::{DefaultRandomCoin, MerkleTree},
+    math::{fields::f128::BaseElement, FieldElement},
+    matrix::ColMatrix,
+    AcceptableOptions, Air, AirContext, Assertion, AuxRandElements, AuxTraceWithMetadata,
+    CompositionPolyTrace, ConstraintCompositionCoefficients, ConstraintEvaluator,
+    DefaultConstraintEvaluator, DefaultTraceLde, EvaluationFrame, Proof,
+    ProofOptions, Prover, StarkDomain, Trace, TraceInfo, TraceLde, TracePolyTable, TraceTable,
+    VerifierError,
+};
+
+mod common;
+use common::{options, policy};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+/// Hashes in the shared proof; its trace has 128 steps.
+const ITERATIONS: usize = 8;
+
+/// Returns a valid proof of [ITERATIONS] hashes of [SEED], generated once for all tests.
+fn valid_proof() -> &'static (PublicInputs, Proof) {
+    static PROOF: OnceLock<(PublicInputs, Proof)> = OnceLock::new();
+    PROOF.get_or_init(|| prove_do_work(SEED, ITERATIONS, options()).unwrap())
+}
+
+fn verify(pub_inputs: PublicInputs, proof: Proof) -> Result<(), Error> {
+    verify_do_work_with::<Blake3>(pub_inputs, proof, &policy()).map(|_| ())
+}
+
+fn bundle_bytes() -> Vec<u8> {
+    let (pub_inputs, proof) = valid_proof().clone();
+    ProofBundle::with_hash_fn(HashFunction::Blake3_256, pub_inputs, proof).to_bytes()
+}
+
+fn element() -> impl Strategy<Value = BaseElement> {
+    any::<u128>().prop_map(BaseElement::new)
+}
+
+// VALID PROOFS
+// ================================================================================================
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(8))]
+
+    #[test]
+    fn proofs_of_random_seeds_verify(seed in [element(), element()], iterations in 1..=16_usize) {
+        let (pub_inputs, proof) = prove_do_work(seed, iterations, options()).unwrap();
+        prop_assert_eq!(seed, pub_inputs.seed);
+        prop_assert_eq!(stark_test::hash_chain(seed, iterations), pub_inputs.result);
+
+        let bytes = ProofBundle::new(pub_inputs, proof.clone()).to_bytes();
+        prop_assert!(verify(pub_inputs, proof).is_ok());
+        prop_assert!(verify_bundle(&bytes, &policy()).is_ok());
+    }
+}
+
+// WRONG PUBLIC INPUTS
+// ================================================================================================
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn wrong_result_is_rejected(column in 0..2_usize, delta in element()) {
+        prop_assume!(delta != BaseElement::ZERO);
+        let (mut pub_inputs, proof) = valid_proof().clone();
+        pub_inputs.result[column] += delta;
+
+        let err = verify(pub_inputs, proof).unwrap_err();
+        prop_assert!(
+            matches!(err, Error::Verifier(VerifierError::InconsistentOodConstraintEvaluations)),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn wrong_seed_is_rejected(column in 0..2_usize, delta in element()) {
+        prop_assume!(delta != BaseElement::ZERO);
+        let (mut pub_inputs, proof) = valid_proof().clone();
+        pub_inputs.seed[column] += delta;
+
+        let err = verify(pub_inputs, proof).unwrap_err();
+        prop_assert!(
+            matches!(err, Error::Verifier(VerifierError::InconsistentOodConstraintEvaluations)),
+            "unexpected error: {err:?}"
+        );
+    }
+
+    #[test]
+    fn wrong_number_of_iterations_is_rejected(iterations in 1..ITERATIONS) {
+        let (mut pub_inputs, proof) = valid_proof().clone();
+        pub_inputs.iterations = iterations;
+
+        // the trace of the proof holds fewer hashes, so the result is asserted at an earlier step
+        let err = verify(pub_inputs, proof).unwrap_err();
+        prop_assert!(
+            matches!(err, Error::Verifier(VerifierError::InconsistentOodConstraintEvaluations)),
+            "unexpected error: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn wrong_trace_length_is_rejected() {
+    let (pub_inputs, proof) = valid_proof();
+    let with_trace_length = |length| {
+        let mut proof = proof.clone();
+        proof.context = Context::new::<BaseElement>(TraceInfo::new(4, length), options());
+        proof
+    };
+
+    // a shorter trace cannot hold the hash chain
+    let err = verify(*pub_inputs, with_trace_length(64)).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+
+    // a longer trace implies a larger evaluation domain than the queries were drawn from
+    for length in [256, 512, 1024] {
+        let err = verify(*pub_inputs, with_trace_length(length)).unwrap_err();
+        assert!(
+            matches!(err, Error::Verifier(VerifierError::ProofDeserializationError(_))),
+            "unexpected error for trace length {length}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn oversized_sponge_message_length_is_rejected() {
+    let msg: Vec<BaseElement> = (0..5).map(BaseElement::new).collect();
+    let (pub_inputs, proof) = prove_sponge(&msg, options()).unwrap();
+    let bytes = ProofBundle::new(pub_inputs, proof).to_bytes();
+
+    // the message length follows the two digest elements; a length of u64::MAX overflows the
+    // digest step, the others do not fit into the trace
+    let msg_len = BUNDLE_HEADER_SIZE + 32..BUNDLE_HEADER_SIZE + 40;
+    for len in [u64::MAX, u64::MAX / 8, 1 << 20] {
+        let mut bytes = bytes.clone();
+        bytes[msg_len.clone()].copy_from_slice(&len.to_le_bytes());
+        let err = verify_bundle(&bytes, &policy()).unwrap_err();
+        assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+    }
+}
+
+// MALFORMED PUBLIC INPUTS
+// ================================================================================================
+
+/// A serialized bundle for an AIR and the range of bytes taken by its public inputs.
+type Bundle = (AirId, Vec<u8>, Range<usize>);
+
+/// Returns valid bundles for every AIR together with the range of bytes taken by their public
+/// inputs, generated once for all tests.
+fn bundles() -> &'static [Bundle] {
+    static BUNDLES: OnceLock<Vec<Bundle>> = OnceLock::new();
+    BUNDLES.get_or_init(|| {
+        let with_range = |air, bytes: Vec<u8>, proof: &Proof| {
+            let end = bytes.len() - ProofSize::of(proof).total();
+            (air, bytes, BUNDLE_HEADER_SIZE..end)
+        };
+
+        let msg: Vec<BaseElement> = (0..5).map(BaseElement::new).collect();
+        let (sponge_inputs, sponge_proof) = prove_sponge(&msg, options()).unwrap();
+        let path = [Hash::new(BaseElement::new(3), BaseElement::new(4)); 3];
+        let leaf = Hash::new(BaseElement::new(1), BaseElement::new(2));
+        let (merkle_inputs, merkle_proof) = prove_membership(leaf, 5, &path, options()).unwrap();
+        let (hiding_inputs, hiding_proof) =
+            prove_hidden_seed(SEED, ITERATIONS, true, options()).unwrap();
+        let seeds = [SEED, [BaseElement::new(1), BaseElement::new(2)]];
+        let (batch_inputs, batch_proof) = prove_batch(&seeds, ITERATIONS, options()).unwrap();
+
+        vec![
+            with_range(AirId::DoWork, bundle_bytes(), &valid_proof().1),
+            with_range(
+                AirId::Sponge,
+                ProofBundle::new(sponge_inputs, sponge_proof.clone()).to_bytes(),
+                &sponge_proof,
+            ),
+            with_range(
+                AirId::Merkle,
+                ProofBundle::new(merkle_inputs, merkle_proof.clone()).to_bytes(),
+                &merkle_proof,
+            ),
+            with_range(
+                AirId::HiddenSeed,
+                ProofBundle::new(hiding_inputs, hiding_proof.clone()).to_bytes(),
+                &hiding_proof,
+            ),
+            with_range(
+                AirId::Batch,
+                ProofBundle::new(batch_inputs, batch_proof.clone()).to_bytes(),
+                &batch_proof,
+            ),
+        ]
+    })
+}
+
+#[test]
+fn bundles_of_every_air_verify() {
+    for (air, bytes, _) in bundles() {
+        let result = verify_bundle(bytes, &policy());
+        assert!(result.is_ok(), "{air:?}: {result:?}");
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn malformed_public_inputs_are_rejected(
+        bundle in any::<Index>(),
+        index in any::<Index>(),
+        mask in 1..=u8::MAX,
+    ) {
+        let (air, bytes, range) = bundle.get(bundles());
+        let mut bytes = bytes.clone();
+        let position = range.start + index.index(range.len());
+        bytes[position] ^= mask;
+
+        // every byte of the public inputs is bound by the proof, and lengths read from them must
+        // be rejected rather than overflow
+        let result = verify_bundle(&bytes, &policy());
+        prop_assert!(result.is_err(), "{air:?} byte {position} was not bound by the proof");
+    }
+}
+
+// DISHONEST TRACES
+// ================================================================================================
+
+/// A trace which skips the check against its AIR performed by debug builds of the prover, so that
+/// proofs of invalid traces reach the verifier.
+struct UncheckedTrace(TraceTable<BaseElement>);
+
+impl Trace for UncheckedTrace {
+    type BaseField = BaseElement;
+
+    fn info(&self) -> &TraceInfo {
+        self.0.info()
+    }
+
+    fn main_segment(&self) -> &ColMatrix<BaseElement> {
+        self.0.main_segment()
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<BaseElement>) {
+        self.0.read_main_frame(row_idx, frame)
+    }
+
+    fn validate<A, E>(&self, _air: &A, _aux: Option<&AuxTraceWithMetadata<E, A::GkrProof>>)
+    where
+        A: Air<BaseField = BaseElement>,
+        E: FieldElement<BaseField = BaseElement>,
+    {
+    }
+}
+
+/// Capacity which the first hash of a dishonest trace starts from.
+const FORGED_CAPACITY: [BaseElement; 2] = [BaseElement::new(7), BaseElement::new(9)];
+
+/// [TrainAir] with the capacity asserted to [FORGED_CAPACITY] instead of zeros at the first step.
+struct ForgedAir(TrainAir);
+
+impl Air for ForgedAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+    type GkrProof = ();
+    type GkrVerifier = ();
+
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        ForgedAir(TrainAir::new(trace_info, pub_inputs, options))
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result)
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<BaseElement>> {
+        let mut assertions = self.0.get_assertions();
+        for (assertion, value) in assertions[2..4].iter_mut().zip(FORGED_CAPACITY) {
+            *assertion = Assertion::single(assertion.column(), 0, value);
+        }
+        assertions
+    }
+
+    fn context(&self) -> &AirContext<BaseElement> {
+        self.0.context()
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<BaseElement>> {
+        self.0.get_periodic_column_values()
+    }
+}
+
+/// Evaluates the constraints of [ForgedAir] while claiming to evaluate those of [TrainAir]; for a
+/// trace starting from [FORGED_CAPACITY], the composition polynomial has the expected degree.
+struct ForgedEvaluator<E: FieldElement<BaseField = BaseElement>> {
+    air: ForgedAir,
+    composition_coefficients: ConstraintCompositionCoefficients<E>,
+}
+
+impl<E: FieldElement<BaseField = BaseElement>> ConstraintEvaluator<E> for ForgedEvaluator<E> {
+    type Air = TrainAir;
+
+    fn evaluate<T: TraceLde<E>>(
+        self,
+        trace: &T,
+        domain: &StarkDomain<BaseElement>,
+    ) -> CompositionPolyTrace<E> {
+        DefaultConstraintEvaluator::new(&self.air, None, self.composition_coefficients)
+            .evaluate(trace, domain)
+    }
+}
+
+/// A [DoWorkProver] which proves any trace given to it, together with the public inputs the trace
+/// attests to.
+struct DishonestProver(DoWorkProver, PublicInputs);
+
+impl Prover for DishonestProver {
+    type BaseField = BaseElement;
+    type Air = TrainAir;
+    type Trace = UncheckedTrace;
+    type HashFn = Blake3;
+    type VC = MerkleTree<Blake3>;
+    type RandomCoin = DefaultRandomCoin<Blake3>;
+    type TraceLde<E: FieldElement<BaseField = BaseElement>> = DefaultTraceLde<E, Blake3, Self::VC>;
+    type ConstraintEvaluator<'a, E: FieldElement<BaseField = BaseElement>> = ForgedEvaluator<E>;
+
+    fn get_pub_inputs(&self, trace: &UncheckedTrace) -> PublicInputs {
+        self.0.get_pub_inputs(&trace.0)
+    }
+
+    fn new_trace_lde<E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        trace_info: &TraceInfo,
+        main_trace: &ColMatrix<BaseElement>,
+        domain: &StarkDomain<BaseElement>,
+        partition_option: PartitionOptions,
+    ) -> (Self::TraceLde<E>, TracePolyTable<E>) {
+        self.0.new_trace_lde(trace_info, main_trace, domain, partition_option)
+    }
+
+    fn new_evaluator<E: FieldElement<BaseField = BaseElement>>(
+        &self,
+        air: &TrainAir,
+        _aux_rand_elements: Option<AuxRandElements<E>>,
+        composition_coefficients: ConstraintCompositionCoefficients<E>,
+    ) -> ForgedEvaluator<E> {
+        let air = ForgedAir::new(air.trace_info().clone(), self.1, air.options().clone());
+        ForgedEvaluator { air, composition_coefficients }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        self.0.options()
+    }
+}
+
+#[test]
+fn nonzero_initial_capacity_is_rejected() {
+    // hash the seed together with a nonzero capacity; every transition of the trace is valid, but
+    // the capacity differs from the zeros asserted at the first step
+    let length = trace_length::<Rescue4x14>(ITERATIONS).unwrap();
+    let mut trace = TraceTable::new(Rescue4x14::STATE_WIDTH, length);
+    trace.fill(
+        |state| {
+            state[..2].copy_from_slice(&SEED);
+            state[2..].copy_from_slice(&FORGED_CAPACITY);
+        },
+        |step, state| {
+            if step % Rescue4x14::CYCLE_LENGTH < Rescue4x14::NUM_ROUNDS {
+                Rescue4x14::apply_round(state, step);
+            } else {
+                state[2..].fill(BaseElement::ZERO);
+            }
+        },
+    );
+
+    let pub_inputs = DoWorkProver::<Rescue4x14, Blake3>::new(options(), ITERATIONS)
+        .get_pub_inputs(&trace);
+    assert_eq!(SEED, pub_inputs.seed);
+    assert_ne!(stark_test::hash_chain(SEED, ITERATIONS), pub_inputs.result);
+    let prover = DishonestProver(DoWorkProver::new(options(), ITERATIONS), pub_inputs);
+    let proof = prover.prove(UncheckedTrace(trace)).unwrap();
+
+    // the proof is valid for the forged capacity, so only the capacity assertions reject it
+    let acceptable = AcceptableOptions::MinConjecturedSecurity(0);
+    let result = winterfell::verify::<
+        ForgedAir,
+        Blake3,
+        DefaultRandomCoin<Blake3>,
+        MerkleTree<Blake3>,
+    >(proof.clone(), pub_inputs, &acceptable);
+    assert!(result.is_ok(), "{result:?}");
+
+    let err = verify(pub_inputs, proof).unwrap_err();
+    assert!(
+        matches!(err, Error::Verifier(VerifierError::InconsistentOodConstraintEvaluations)),
+        "unexpected error: {err:?}"
+    );
+}
+
+// TAMPERED PROOF BYTES
+// ================================================================================================
+
+/// A part of a serialized proof bundle.
+#[derive(Debug, Clone, Copy)]
+enum Region {
+    Context,
+    Commitments,
+    TraceOpenings,
+    ConstraintOpenings,
+    OodFrame,
+    FriLayers,
+    FriRemainder,
+    PowNonce,
+}
+
+impl Region {
+    const ALL: [Region; 8] = [
+        Region::Context,
+        Region::Commitments,
+        Region::TraceOpenings,
+        Region::ConstraintOpenings,
+        Region::OodFrame,
+        Region::FriLayers,
+        Region::FriRemainder,
+        Region::PowNonce,
+    ];
+
+    /// Returns the range of bytes taken by this region in a serialized bundle.
+    fn range(self, bundle: &[u8], size: &ProofSize) -> Range<usize> {
+        let parts = [
+            size.context,
+            size.commitments,
+            size.trace_openings,
+            size.constraint_openings,
+            size.ood_frame,
+            size.fri_layers,
+            size.fri_remainder,
+            size.pow_nonce,
+        ];
+        let index = self as usize;
+        let start = bundle.len() - size.total() + parts[..index].iter().sum::<usize>();
+        start..start + parts[index]
+    }
+
+    /// Returns true if the error is what a modification of a byte in this region should cause.
+    fn explains(self, err: &Error) -> bool {
+        use VerifierError::*;
+
+        // length prefixes and node counts are part of every region
+        let malformed = matches!(
+            err,
+            Error::Deserialization(_)
+                | Error::Bundle(BundleError::Truncated | BundleError::TrailingBytes)
+                | Error::Verifier(ProofDeserializationError(_))
+        );
+        let Error::Verifier(err) = err else {
+            return malformed
+                || matches!((self, err), (Region::Context, Error::PublicInputMismatch(_)));
+        };
+        malformed
+            || match self {
+                // the trace length and options determine the domains and the public coin seed
+                Region::Context => matches!(
+                    err,
+                    InconsistentBaseField
+                        | InconsistentOodConstraintEvaluations
+                        | TraceQueryDoesNotMatchCommitment
+                ),
+                // commitments are drawn into the public coin before any other check
+                Region::Commitments => matches!(
+                    err,
+                    InconsistentOodConstraintEvaluations
+                        | TraceQueryDoesNotMatchCommitment
+                        | ConstraintQueryDoesNotMatchCommitment
+                        | FriVerificationFailed(_)
+                ),
+                Region::TraceOpenings => matches!(err, TraceQueryDoesNotMatchCommitment),
+                Region::ConstraintOpenings => matches!(err, ConstraintQueryDoesNotMatchCommitment),
+                Region::OodFrame => matches!(err, InconsistentOodConstraintEvaluations),
+                Region::FriLayers | Region::FriRemainder => matches!(err, FriVerificationFailed(_)),
+                // without grinding, the nonce only changes the queried positions
+                Region::PowNonce => matches!(
+                    err,
+                    QuerySeedProofOfWorkVerificationFailed | TraceQueryDoesNotMatchCommitment
+                ),
+            }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn tampered_proof_byte_is_rejected(
+        region in proptest::sample::select(&Region::ALL[..]),
+        index in any::<Index>(),
+        mask in 1..=u8::MAX,
+    ) {
+        let mut bytes = bundle_bytes();
+        let range = region.range(&bytes, &ProofSize::of(&valid_proof().1));
+        let position = range.start + index.index(range.len());
+        bytes[position] ^= mask;
+
+        let err = verify_bundle(&bytes, &policy()).unwrap_err();
+        prop_assert!(
+            region.explains(&err),
+            "unexpected error for {region:?} byte {position}: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn malformed_proof_context_is_rejected() {
+    let bytes = bundle_bytes();
+    let context = Region::Context.range(&bytes, &ProofSize::of(&valid_proof().1));
+
+    // auxiliary random elements without an auxiliary trace segment, a trace length of 2^200,
+    // LDE domains of 2^38 * 8 and 2^62 * 8 steps, a different field modulus, a blowup factor
+    // which is not a power of two, and two partitions; the blowup factor follows 6 bytes of trace
+    // info, 17 bytes of field modulus and the number of queries
+    let cases = [(2, 1), (3, 200), (3, 38), (3, 62), (7, 0), (24, 3), (29, 2)];
+    for (offset, value) in cases {
+        let mut bytes = bytes.clone();
+        bytes[context.start + offset] = value;
+        let err = verify_bundle(&bytes, &policy()).unwrap_err();
+        assert!(matches!(err, Error::Deserialization(_)), "unexpected error: {err:?}");
+    }
+}
+
+#[test]
+fn oversized_fri_partition_count_is_rejected() {
+    // the number of FRI partitions is stored as its log2 in the last byte of the FRI proof
+    let mut bytes = bundle_bytes();
+    let size = ProofSize::of(&valid_proof().1);
+    let position = Region::FriRemainder.range(&bytes, &size).end - 1;
+    bytes[position] = u8::MAX;
+
+    let err = verify_bundle(&bytes, &policy()).unwrap_err();
+    assert!(matches!(err, Error::Deserialization(_)), "unexpected error: {err:?}");
+}
+
+#[test]
+fn missing_query_openings_are_rejected() {
+    // the number of unique queries is stored in the last byte of the context region
+    let mut bytes = bundle_bytes();
+    let position = Region::Context.range(&bytes, &ProofSize::of(&valid_proof().1)).end - 1;
+    bytes[position] = 0;
+
+    let err = verify_bundle(&bytes, &policy()).unwrap_err();
+    assert!(matches!(err, Error::Deserialization(_)), "unexpected error: {err:?}");
+}
+
+#[test]
+fn oversized_merkle_proof_depth_is_rejected() {
+    // the depth of the authentication paths of the first trace queries follows the queried
+    // values and the length of the serialized paths
+    let queries = valid_proof().1.trace_queries[0].to_bytes();
+    let mut source = SliceReader::new(&queries);
+    let values = Vec::<u8>::read_from(&mut source).unwrap();
+    let paths = Vec::<u8>::read_from(&mut source).unwrap();
+    let offset = values.to_bytes().len() + paths.to_bytes().len() - paths.len();
+
+    let bytes = bundle_bytes();
+    let position = Region::TraceOpenings.range(&bytes, &ProofSize::of(&valid_proof().1)).start;
+    for depth in [64, u8::MAX] {
+        let mut bytes = bytes.clone();
+        bytes[position + offset] = depth;
+        let err = verify_bundle(&bytes, &policy()).unwrap_err();
+        assert!(matches!(err, Error::Deserialization(_)), "unexpected error: {err:?}");
+    }
+}
+
+#[test]
+fn blowup_factor_below_constraint_degree_is_rejected() {
+    // constraints of degree 3 need a blowup factor of at least 4
+    let mut bytes = bundle_bytes();
+    let context = Region::Context.range(&bytes, &ProofSize::of(&valid_proof().1));
+    bytes[context.start + 24] = 2;
+
+    let err = verify_bundle(&bytes, &policy()).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+}
@@ -0,0 +1,64 @@
+use stark_test::sponge::{prove_sponge, verify_sponge_with, SpongePublicInputs};
+use stark_test::utils::rescue::{Rescue128, RATE_WIDTH};
+use stark_test::Error;
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    FieldExtension, ProofOptions, VerifierError,
+};
+
+mod common;
+use common::{options, policy};
+
+fn message(len: usize) -> Vec<BaseElement> {
+    (0..len as u128).map(|i| BaseElement::new(i * i + 7)).collect()
+}
+
+#[test]
+fn proofs_of_messages_verify() {
+    // empty, partial block, exact block, several exact blocks and several blocks with a partial
+    // last block
+    for len in [0, 3, RATE_WIDTH, 3 * RATE_WIDTH, 2 * RATE_WIDTH + 1] {
+        let msg = message(len);
+        let (pub_inputs, proof) = prove_sponge(&msg, options()).unwrap();
+        let expected = SpongePublicInputs { digest: Rescue128::digest(&msg), msg_len: len };
+        assert_eq!(expected, pub_inputs);
+        let result = verify_sponge_with(pub_inputs, proof, &policy());
+        assert!(result.is_ok(), "message of {len} elements: {result:?}");
+    }
+}
+
+#[test]
+fn wrong_digest_is_rejected() {
+    for len in [0, 3, RATE_WIDTH, 3 * RATE_WIDTH] {
+        let (mut pub_inputs, proof) = prove_sponge(&message(len), options()).unwrap();
+        pub_inputs.digest = Rescue128::digest(&message(len + 1));
+        let err = verify_sponge_with(pub_inputs, proof, &policy()).unwrap_err();
+        assert!(
+            matches!(err, Error::Verifier(VerifierError::InconsistentOodConstraintEvaluations)),
+            "unexpected error for message of {len} elements: {err:?}"
+        );
+    }
+}
+
+#[test]
+fn wrong_message_length_is_rejected() {
+    // the padding of the last block depends on the length of the message
+    let (mut pub_inputs, proof) = prove_sponge(&message(3), options()).unwrap();
+    pub_inputs.msg_len = 2;
+    assert!(verify_sponge_with(pub_inputs, proof, &policy()).is_err());
+
+    // a padded message cannot pass for one filling its last block, even if the padding matches
+    let mut msg = message(3);
+    let (pub_inputs, proof) = prove_sponge(&msg, options()).unwrap();
+    msg.push(BaseElement::ONE);
+    let claimed = SpongePublicInputs { digest: pub_inputs.digest, msg_len: msg.len() };
+    assert!(verify_sponge_with(claimed, proof, &policy()).is_err());
+}
+
+#[test]
+fn blowup_factor_below_constraint_degree_is_rejected() {
+    // constraints of degree 5 need a blowup factor of at least 8
+    let options = ProofOptions::new(28, 4, 0, FieldExtension::None, 8, 31);
+    let err = prove_sponge(&message(3), options).unwrap_err();
+    assert!(matches!(err, Error::PublicInputMismatch(_)), "unexpected error: {err:?}");
+}
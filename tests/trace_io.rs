@@ -0,0 +1,120 @@
+use stark_test::debug::{check_constraints, ConstraintError, NamedTrace, TraceFormatError};
+use stark_test::{build_trace, result_step, PublicInputs, Rescue4x14, TrainAir};
+use winterfell::{
+    math::{fields::f128::BaseElement, StarkField},
+    Air, FieldExtension, ProofOptions, Trace, TraceTable,
+};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+const ITERATIONS: usize = 2;
+
+fn named_trace() -> NamedTrace {
+    let trace = build_trace::<Rescue4x14>(SEED, ITERATIONS);
+    NamedTrace::with_names(trace, &["h0", "h1", "c0", "c1"])
+}
+
+fn assert_same_trace(expected: &TraceTable<BaseElement>, actual: &TraceTable<BaseElement>) {
+    assert_eq!(expected.width(), actual.width());
+    assert_eq!(expected.length(), actual.length());
+    for column in 0..expected.width() {
+        assert_eq!(expected.get_column(column), actual.get_column(column));
+    }
+}
+
+/// Returns a CSV trace of the specified length with a single column.
+fn single_column_csv(length: usize) -> String {
+    let rows: String = (0..length).map(|step| format!("{step},{step}\n")).collect();
+    format!("step,x\n{rows}")
+}
+
+#[test]
+fn csv_round_trip() {
+    let named = named_trace();
+    let csv = named.to_csv();
+    let mut lines = csv.lines();
+    assert_eq!(Some("step,h0,h1,c0,c1"), lines.next());
+    assert_eq!(Some("0,42,43,0,0"), lines.next());
+    assert_eq!(named.trace.length() - 1, lines.count());
+
+    let imported = NamedTrace::from_csv(&csv).unwrap();
+    assert_eq!(named.names, imported.names);
+    assert_same_trace(&named.trace, &imported.trace);
+}
+
+#[test]
+fn json_round_trip() {
+    let named = NamedTrace::new(build_trace::<Rescue4x14>(SEED, ITERATIONS));
+    let json = named.to_json();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::json!(["r0", "r1", "r2", "r3"]), value["columns"]);
+    let first_row = serde_json::json!({ "step": 0, "values": ["42", "43", "0", "0"] });
+    assert_eq!(first_row, value["rows"][0]);
+
+    let imported = NamedTrace::from_json(&json).unwrap();
+    assert_eq!(named.names, imported.names);
+    assert_same_trace(&named.trace, &imported.trace);
+}
+
+#[test]
+fn hand_edited_trace_fails_constraint_check() {
+    let named = named_trace();
+    let step = result_step::<Rescue4x14>(ITERATIONS);
+    let result = [named.trace.get(0, step), named.trace.get(1, step)];
+    let pub_inputs = PublicInputs { seed: SEED, result, iterations: ITERATIONS };
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 8, 31);
+    let air: TrainAir = TrainAir::new(named.trace.info().clone(), pub_inputs, options);
+
+    // replace the value of c0 at step 3 with a hexadecimal number
+    let csv: Vec<String> = named.to_csv().lines().map(String::from).collect();
+    let mut row: Vec<&str> = csv[4].split(',').collect();
+    row[3] = "0x2a";
+    let edited = [&csv[..4], &[row.join(",")], &csv[5..]].concat().join("\n");
+
+    let imported = NamedTrace::from_csv(&edited).unwrap();
+    assert_eq!(BaseElement::new(42), imported.trace.get(2, 3));
+    let err = check_constraints(&air, &imported.trace).unwrap_err();
+    assert!(matches!(err, ConstraintError::Transition { step: 2, .. }), "unexpected: {err}");
+}
+
+#[test]
+fn malformed_csv_is_rejected() {
+    assert_eq!(Err(TraceFormatError::MissingHeader), NamedTrace::from_csv("").map(|_| ()));
+    let csv = single_column_csv(8).replacen("step", "index", 1);
+    assert_eq!(Err(TraceFormatError::MissingHeader), NamedTrace::from_csv(&csv).map(|_| ()));
+
+    let result = NamedTrace::from_csv("step\n0\n").map(|_| ());
+    assert_eq!(Err(TraceFormatError::InvalidWidth(0)), result);
+
+    let result = NamedTrace::from_csv(&single_column_csv(12)).map(|_| ());
+    assert_eq!(Err(TraceFormatError::InvalidLength(12)), result);
+    let result = NamedTrace::from_csv(&single_column_csv(4)).map(|_| ());
+    assert_eq!(Err(TraceFormatError::InvalidLength(4)), result);
+
+    let csv = single_column_csv(8).replace("3,3\n", "");
+    let expected = TraceFormatError::UnexpectedStep { expected: 3, step: "4".into() };
+    assert_eq!(Err(expected), NamedTrace::from_csv(&csv).map(|_| ()));
+
+    let csv = single_column_csv(8).replace("5,5\n", "5,5,5\n");
+    let expected = TraceFormatError::RowWidthMismatch { step: 5, expected: 1, actual: 2 };
+    assert_eq!(Err(expected), NamedTrace::from_csv(&csv).map(|_| ()));
+
+    let modulus = BaseElement::MODULUS.to_string();
+    let csv = single_column_csv(8).replace("6,6\n", &format!("6,{modulus}\n"));
+    let err = NamedTrace::from_csv(&csv).map(|_| ()).unwrap_err();
+    let TraceFormatError::InvalidElement { step, column, .. } = err else {
+        panic!("expected an invalid element, but got: {err}");
+    };
+    assert_eq!((6, "x"), (step, column.as_str()));
+}
+
+#[test]
+fn malformed_json_is_rejected() {
+    let result = NamedTrace::from_json("{\"columns\": [\"x\"]}").map(|_| ());
+    assert!(matches!(result, Err(TraceFormatError::MalformedJson(_))));
+
+    let rows: Vec<String> =
+        (0..8).map(|step| format!("{{\"step\": {}, \"values\": [\"1\"]}}", 7 - step)).collect();
+    let json = format!("{{\"columns\": [\"x\"], \"rows\": [{}]}}", rows.join(","));
+    let expected = TraceFormatError::UnexpectedStep { expected: 0, step: "7".into() };
+    assert_eq!(Err(expected), NamedTrace::from_json(&json).map(|_| ()));
+}
@@ -0,0 +1,143 @@
+use stark_test::batch::BatchProver;
+use stark_test::debug::{
+    find_under_constrained_cells, CellFreedom, ConstraintError, UnderConstrainedCell,
+};
+use stark_test::hiding::{self, HiddenSeedProver};
+use stark_test::merkle::{self, MerkleProver};
+use stark_test::sponge::{self, SpongeProver};
+use stark_test::utils::rescue::{self, Hash};
+use stark_test::{
+    batch, build_trace, result_step, Blake3, DoWorkProver, Rescue4x14, Rescue6x7, TrainAir,
+};
+use winterfell::{
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, FieldExtension, ProofOptions, Prover, Trace, TraceTable,
+};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+fn options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 8, 31)
+}
+
+/// Instantiates the AIR of `prover` for the trace and returns its under-constrained cells.
+fn analyze<P>(prover: &P, trace: &TraceTable<BaseElement>) -> Vec<UnderConstrainedCell>
+where
+    P: Prover<BaseField = BaseElement, Trace = TraceTable<BaseElement>>,
+{
+    let pub_inputs = prover.get_pub_inputs(trace);
+    let air = P::Air::new(trace.info().clone(), pub_inputs, prover.options().clone());
+    find_under_constrained_cells(&air, trace).unwrap()
+}
+
+fn free_inputs(step: usize, columns: impl IntoIterator<Item = usize>) -> Vec<UnderConstrainedCell> {
+    columns
+        .into_iter()
+        .map(|column| UnderConstrainedCell { step, column, freedom: CellFreedom::FreeInput })
+        .collect()
+}
+
+#[test]
+fn hash_chain_state_is_determined_by_seed() {
+    // the seed and the zero capacity are asserted at step 0, and every later row follows from it
+    let trace = build_trace::<Rescue4x14>(SEED, 5);
+    let prover = DoWorkProver::<Rescue4x14, Blake3>::new(options(), 5);
+    assert_eq!(Vec::<UnderConstrainedCell>::new(), analyze(&prover, &trace));
+
+    let trace = build_trace::<Rescue6x7>(SEED, 5);
+    let prover = DoWorkProver::<Rescue6x7, Blake3>::new(options(), 5);
+    assert_eq!(Vec::<UnderConstrainedCell>::new(), analyze(&prover, &trace));
+}
+
+#[test]
+fn batch_state_is_determined_by_seeds() {
+    // the seed and the zero capacity of every chain are asserted at step 0
+    let trace = batch::build_trace::<Rescue4x14>(&[SEED, [SEED[1], SEED[0]]], 3);
+    let prover = BatchProver::<Rescue4x14>::new(options(), 2, 3);
+    assert_eq!(Vec::<UnderConstrainedCell>::new(), analyze(&prover, &trace));
+
+    // chains which start at later segments are asserted at the first step of their segment
+    let seeds: Vec<[BaseElement; 2]> =
+        (0..70).map(|i| [BaseElement::new(i), BaseElement::new(i + 1)]).collect();
+    let trace = batch::build_trace::<Rescue4x14>(&seeds, 1);
+    assert_eq!(2 * 16, trace.length());
+    let prover = BatchProver::<Rescue4x14>::new(options(), seeds.len(), 1);
+    assert_eq!(Vec::<UnderConstrainedCell>::new(), analyze(&prover, &trace));
+}
+
+#[test]
+fn sponge_state_is_determined_by_message() {
+    let msg: Vec<BaseElement> = (0..7).map(BaseElement::new).collect();
+    let trace = sponge::build_trace(&msg);
+    let cells = analyze(&SpongeProver::new(options(), msg.len()), &trace);
+
+    // message columns are free on the first step of a cycle, where blocks are absorbed, and
+    // ignored on all other steps
+    assert!(!cells.is_empty());
+    for cell in cells {
+        assert!(cell.column >= rescue::STATE_WIDTH, "{cell}");
+        let expected = if cell.step % rescue::CYCLE_LENGTH == 0 {
+            CellFreedom::FreeInput
+        } else {
+            CellFreedom::Unconstrained
+        };
+        assert_eq!(expected, cell.freedom, "{cell}");
+    }
+}
+
+#[test]
+fn merkle_state_is_free_only_where_nodes_enter_and_blinding_rows_are_unconstrained() {
+    let leaf = Hash::new(BaseElement::new(1), BaseElement::new(2));
+    let path: Vec<Hash> =
+        (3..6).map(|i| Hash::new(BaseElement::new(i), BaseElement::new(i + 1))).collect();
+    let trace = merkle::build_trace(leaf, 5, &path, 4, &mut rand::thread_rng());
+    let cells = analyze(&MerkleProver::new(options(), path.len()), &trace);
+
+    // the leaf and the siblings enter the state at the start of a cycle; apart from them, only
+    // cells of the direction bit column are free up to the root, and the rows after the root
+    // are random
+    let root_step = path.len() * rescue::CYCLE_LENGTH - 1;
+    let (path_cells, blinding_cells): (Vec<_>, Vec<_>) =
+        cells.iter().partition(|cell| cell.step <= root_step);
+    for cell in path_cells.iter().filter(|cell| cell.column != merkle::BIT_COLUMN) {
+        assert_eq!(0, cell.step % rescue::CYCLE_LENGTH, "{cell}");
+        assert_eq!(CellFreedom::FreeInput, cell.freedom, "{cell}");
+    }
+    assert_eq!(free_inputs(0, 0..4), cells[..4]);
+    for cell in &blinding_cells {
+        assert_eq!(CellFreedom::Unconstrained, cell.freedom, "{cell}");
+    }
+    assert_eq!((trace.length() - root_step - 1) * trace.width(), blinding_cells.len());
+}
+
+#[test]
+fn hidden_seed_is_free_and_blinding_rows_are_unconstrained() {
+    let iterations = 3;
+    let trace = hiding::build_trace(SEED, iterations, 4, &mut rand::thread_rng());
+    let cells = analyze(&HiddenSeedProver::new(options(), iterations, true), &trace);
+
+    // the seed is private, and the rows after the result are random
+    assert_eq!(free_inputs(0, 0..2), cells[..2]);
+    assert_eq!(
+        "column 0 at step 0 is a free input: it is not determined by assertions or earlier rows",
+        cells[0].to_string()
+    );
+    let result_step = result_step::<Rescue4x14>(iterations);
+    for cell in &cells[2..] {
+        assert!(cell.step > result_step, "{cell}");
+        assert_eq!(CellFreedom::Unconstrained, cell.freedom, "{cell}");
+    }
+    assert_eq!((trace.length() - result_step - 1) * trace.width(), cells.len() - 2);
+}
+
+#[test]
+fn invalid_trace_is_rejected() {
+    let mut trace = build_trace::<Rescue4x14>(SEED, 5);
+    let prover = DoWorkProver::<Rescue4x14, Blake3>::new(options(), 5);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let air: TrainAir = TrainAir::new(trace.info().clone(), pub_inputs, options());
+    trace.set(2, 21, trace.get(2, 21) + BaseElement::ONE);
+
+    let err = find_under_constrained_cells(&air, &trace).unwrap_err();
+    assert!(matches!(err, ConstraintError::Transition { step: 20, .. }), "{err}");
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use stark_test::utils::rescue::Hash;
+use stark_test::{batch, hiding, merkle, prove_do_work, sponge, ProofBundle};
+use winterfell::{math::fields::f128::BaseElement, FieldExtension, ProofOptions};
+
+const SEED: [BaseElement; 2] = [BaseElement::new(42), BaseElement::new(43)];
+
+/// Proofs generated with these options have a conjectured security level below 100 bits.
+fn options() -> ProofOptions {
+    ProofOptions::new(20, 8, 0, FieldExtension::None, 8, 31)
+}
+
+/// Creates an empty directory for the test with the specified name.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("verify_batch-{}-{name}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Writes a bundle for every AIR of the crate into `dir`.
+fn write_mixed_bundles(dir: &Path) {
+    let (pub_inputs, proof) = prove_do_work(SEED, 4, options()).unwrap();
+    fs::write(dir.join("do_work.bin"), ProofBundle::new(pub_inputs, proof).to_bytes()).unwrap();
+
+    let msg: Vec<BaseElement> = (0..5).map(BaseElement::new).collect();
+    let (pub_inputs, proof) = sponge::prove_sponge(&msg, options()).unwrap();
+    fs::write(dir.join("sponge.bin"), ProofBundle::new(pub_inputs, proof).to_bytes()).unwrap();
+
+    let leaf = Hash::new(BaseElement::new(1), BaseElement::new(2));
+    let path = [Hash::new(BaseElement::new(3), BaseElement::new(4))];
+    let (pub_inputs, proof) = merkle::prove_membership(leaf, 1, &path, options()).unwrap();
+    fs::write(dir.join("merkle.bin"), ProofBundle::new(pub_inputs, proof).to_bytes()).unwrap();
+
+    let (pub_inputs, proof) = hiding::prove_hidden_seed(SEED, 2, true, options()).unwrap();
+    fs::write(dir.join("hidden.bin"), ProofBundle::new(pub_inputs, proof).to_bytes()).unwrap();
+
+    let (pub_inputs, proof) = batch::prove_batch(&[SEED], 2, options()).unwrap();
+    fs::write(dir.join("batch.bin"), ProofBundle::new(pub_inputs, proof).to_bytes()).unwrap();
+}
+
+/// Runs the verify_batch binary with JSON output, and returns its exit code and output lines.
+fn verify_batch(args: &[&str]) -> (i32, Vec<serde_json::Value>) {
+    let output = Command::new(env!("CARGO_BIN_EXE_verify_batch"))
+        .arg("--json")
+        .args(args)
+        .output()
+        .unwrap();
+    let lines = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    (output.status.code().unwrap(), lines)
+}
+
+#[test]
+fn security_policy_applies_to_every_air() {
+    let dir = test_dir("policy");
+    write_mixed_bundles(&dir);
+    let dir = dir.to_str().unwrap();
+
+    let (code, lines) = verify_batch(&[dir, "--min-conjectured-security", "0"]);
+    assert_eq!(0, code);
+    assert_eq!(5, lines[5]["summary"]["passed"]);
+
+    let (code, lines) = verify_batch(&[dir, "--min-conjectured-security", "100"]);
+    assert_eq!(1, code);
+    for line in &lines[..5] {
+        assert_eq!("fail", line["status"], "{line}");
+        assert_eq!(9, line["exit_code"], "{line}");
+    }
+    assert_eq!(5, lines[5]["summary"]["failed"]);
+}
+
+#[test]
+fn corrupted_bundle_fails_the_batch() {
+    let dir = test_dir("corrupted");
+    let (pub_inputs, proof) = prove_do_work(SEED, 4, options()).unwrap();
+    let bytes = ProofBundle::new(pub_inputs, proof).to_bytes();
+    fs::write(dir.join("a_good.bin"), &bytes).unwrap();
+    let mut corrupted = bytes;
+    let middle = corrupted.len() / 2;
+    corrupted[middle] ^= 0x55;
+    fs::write(dir.join("b_corrupted.bin"), corrupted).unwrap();
+
+    let (code, lines) = verify_batch(&[dir.to_str().unwrap(), "--min-conjectured-security", "0"]);
+    assert_eq!(1, code);
+
+    // one record per file in the order of their names, followed by the summary
+    assert_eq!(3, lines.len());
+    assert_eq!("pass", lines[0]["status"]);
+    assert!(lines[0]["file"].as_str().unwrap().ends_with("a_good.bin"));
+    assert_eq!("fail", lines[1]["status"]);
+    assert!(lines[1]["file"].as_str().unwrap().ends_with("b_corrupted.bin"));
+    let summary = &lines[2]["summary"];
+    assert_eq!(2, summary["total"]);
+    assert_eq!(1, summary["passed"]);
+    assert_eq!(1, summary["failed"]);
+}